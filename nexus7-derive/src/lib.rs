@@ -0,0 +1,411 @@
+//! Derive macros for nexus7.
+//!
+//! `#[derive(HclResource)]` generates the block conversion for a resource or data source
+//! struct. The struct is configured with `#[hcl(...)]` attributes:
+//!
+//! - `#[hcl(resource = "aws_vpc")]` or `#[hcl(data = "aws_vpc")]` on the struct sets the
+//!   block type. Resources get a `TryFrom<T> for hcl::Block` impl that calls the struct's
+//!   `validate` method first, data sources get a `From` impl.
+//! - `#[hcl(label)]` marks the field holding the block name and implements `Resource` or
+//!   `DataSource` with it. Without it the trait must be implemented by hand. A label is
+//!   only written as an attribute when it is also given a `rename`, and the block label is
+//!   sanitized into an HCL identifier the same way as references to the block, so a name
+//!   such as `logs.example.com` is labelled `logs_example_com`.
+//! - `#[hcl(rename = "bucket")]` sets the attribute name, which defaults to the field name.
+//! - `#[hcl(default = "expr")]` renders `expr` when an optional field is `None`.
+//! - `#[hcl(block)]` renders a field, or each element of an optional or repeated field, as
+//!   a nested block through `From<T> for hcl::Block`. `#[hcl(block = "accepter")]` also
+//!   renames the block, for types used as several blocks of one resource.
+//! - `#[hcl(reference = "id")]` renders a reference to another resource's attribute, such as
+//!   `vpc_id = aws_vpc.main.id`. The attribute name defaults to `<field>_<attribute>`, and
+//!   `Vec<&T>` fields render as a list of references.
+//! - `#[hcl(flatten)]` renders a field in place through its
+//!   `apply(self, BlockBuilder) -> BlockBuilder` method, for values such as route targets
+//!   whose attribute name depends on the variant. `#[hcl(flatten = "method")]` calls another
+//!   method with the same signature.
+//! - `#[hcl(meta)]` marks the `MetaArguments` field, which is rendered last.
+//! - `#[hcl(skip)]` leaves a field out of the block.
+//!
+//! `block`, `reference`, `flatten`, `meta` and `skip` cannot be combined with each other, with
+//! `label` or `default`, or with `rename` except for `reference`.
+//!
+//! Other fields are rendered as attributes through `IntoExpression`, and optional fields are
+//! skipped when `None`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Expr, Field, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Type,
+};
+
+#[proc_macro_derive(HclResource, attributes(hcl))]
+pub fn derive_hcl_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Kind {
+    Resource(LitStr),
+    Data(LitStr),
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    label: bool,
+    rename: Option<LitStr>,
+    default: Option<Expr>,
+    block: bool,
+    block_name: Option<LitStr>,
+    reference: Option<LitStr>,
+    flatten: Option<Ident>,
+    meta: bool,
+    skip: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let kind = struct_kind(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "HclResource requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "HclResource can only be derived for structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut label = None;
+    let mut body = Vec::new();
+    let mut meta = None;
+
+    for field in fields {
+        let options = field_options(field)?;
+        let ident = field.ident.as_ref().expect("named field");
+
+        if options.label {
+            if label.is_some() {
+                return Err(syn::Error::new_spanned(ident, "duplicate `label` field"));
+            }
+            label = Some(ident.clone());
+            if options.rename.is_none() {
+                continue;
+            }
+        }
+
+        if options.skip {
+            continue;
+        }
+
+        if let Some(method) = &options.flatten {
+            body.push(quote! { __block = __value.#ident.#method(__block); });
+            continue;
+        }
+
+        if options.meta {
+            if meta.is_some() {
+                return Err(syn::Error::new_spanned(ident, "duplicate `meta` field"));
+            }
+            meta = Some(quote! { __block = __value.#ident.apply(__block); });
+            continue;
+        }
+
+        body.push(render_field(field, ident, &options)?);
+    }
+
+    let (trait_path, type_name, identifier) = match &kind {
+        Kind::Resource(type_name) => (quote!(::nexus7::Resource), type_name, "resource"),
+        Kind::Data(type_name) => (quote!(::nexus7::DataSource), type_name, "data"),
+    };
+
+    let trait_impl = label.map(|label| {
+        quote! {
+            impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+                const TYPE: &'static str = #type_name;
+
+                fn name(&self) -> &str {
+                    &self.#label
+                }
+            }
+        }
+    });
+
+    let build = quote! {
+        let __label = ::nexus7::__private::hcl::Identifier::sanitized(
+            <#name #ty_generics as #trait_path>::name(&__value),
+        )
+        .into_inner();
+        let mut __block = ::nexus7::__private::hcl::Block::builder(#identifier)
+            .add_label(<#name #ty_generics as #trait_path>::TYPE)
+            .add_label(__label);
+        #(#body)*
+        #meta
+    };
+
+    let conversion = match kind {
+        Kind::Resource(_) => quote! {
+            impl #impl_generics ::core::convert::TryFrom<#name #ty_generics>
+                for ::nexus7::__private::hcl::Block #where_clause
+            {
+                type Error = ::nexus7::Error;
+
+                fn try_from(__value: #name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    __value.validate()?;
+                    #build
+                    ::core::result::Result::Ok(__block.build())
+                }
+            }
+        },
+        Kind::Data(_) => quote! {
+            impl #impl_generics ::core::convert::From<#name #ty_generics>
+                for ::nexus7::__private::hcl::Block #where_clause
+            {
+                fn from(__value: #name #ty_generics) -> Self {
+                    #build
+                    __block.build()
+                }
+            }
+        },
+    };
+
+    Ok(quote! {
+        #trait_impl
+        #conversion
+    })
+}
+
+fn struct_kind(input: &DeriveInput) -> syn::Result<Kind> {
+    let mut kind = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("hcl")) {
+        attr.parse_nested_meta(|meta| {
+            if kind.is_some() {
+                return Err(meta.error("only one of `resource` and `data` can be set"));
+            }
+            if meta.path.is_ident("resource") {
+                kind = Some(Kind::Resource(meta.value()?.parse()?));
+            } else if meta.path.is_ident("data") {
+                kind = Some(Kind::Data(meta.value()?.parse()?));
+            } else {
+                return Err(meta.error("expected `resource` or `data`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    kind.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[hcl(resource = \"...\")]` or `#[hcl(data = \"...\")]`",
+        )
+    })
+}
+
+fn field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    let mut seen: Vec<&'static str> = Vec::new();
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("hcl")) {
+        attr.parse_nested_meta(|meta| {
+            let name = if meta.path.is_ident("label") {
+                options.label = true;
+                "label"
+            } else if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse()?);
+                "rename"
+            } else if meta.path.is_ident("default") {
+                let expr: LitStr = meta.value()?.parse()?;
+                options.default = Some(expr.parse()?);
+                "default"
+            } else if meta.path.is_ident("block") {
+                options.block = true;
+                if let Ok(value) = meta.value() {
+                    options.block_name = Some(value.parse()?);
+                }
+                "block"
+            } else if meta.path.is_ident("reference") {
+                options.reference = Some(meta.value()?.parse()?);
+                "reference"
+            } else if meta.path.is_ident("flatten") {
+                let method = match meta.value() {
+                    Ok(value) => value.parse::<LitStr>()?.parse()?,
+                    Err(_) => Ident::new("apply", meta.path.span()),
+                };
+                options.flatten = Some(method);
+                "flatten"
+            } else if meta.path.is_ident("meta") {
+                options.meta = true;
+                "meta"
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                "skip"
+            } else {
+                return Err(meta.error("unknown `hcl` field attribute"));
+            };
+
+            if seen.contains(&name) {
+                return Err(meta.error(format!("duplicate `{}` attribute", name)));
+            }
+            if let Some(other) = seen.iter().find(|other| conflicts(name, other)) {
+                return Err(meta.error(format!("`{}` conflicts with `{}`", name, other)));
+            }
+            seen.push(name);
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Whether two field attributes cannot be combined. `block`, `reference`, `flatten`, `meta`
+/// and `skip` each decide how the field is rendered, so at most one applies; `label`,
+/// `rename` and `default` only make sense alongside some of them.
+fn conflicts(first: &str, second: &str) -> bool {
+    const MODES: [&str; 5] = ["block", "reference", "flatten", "meta", "skip"];
+
+    let (mode, other) = match (MODES.contains(&first), MODES.contains(&second)) {
+        (true, true) => return true,
+        (true, false) => (first, second),
+        (false, true) => (second, first),
+        (false, false) => return false,
+    };
+    match other {
+        "rename" => mode != "reference",
+        _ => true,
+    }
+}
+
+fn render_field(field: &Field, ident: &Ident, options: &FieldOptions) -> syn::Result<TokenStream2> {
+    let optional = inner_type(&field.ty, "Option");
+    let ty = optional.unwrap_or(&field.ty);
+
+    if options.block {
+        let convert = match &options.block_name {
+            Some(name) => quote! {
+                |__item| {
+                    let mut __nested = ::nexus7::__private::hcl::Block::from(__item);
+                    __nested.identifier = ::nexus7::__private::hcl::Identifier::unchecked(#name);
+                    __nested
+                }
+            },
+            None => quote! { ::nexus7::__private::hcl::Block::from },
+        };
+        let add = match inner_type(ty, "Vec") {
+            Some(_) => quote! {
+                __block = __block.add_blocks(__field.into_iter().map(#convert));
+            },
+            None => quote! {
+                __block = __block.add_block((#convert)(__field));
+            },
+        };
+        return Ok(wrap_optional(ident, optional.is_some(), None, add));
+    }
+
+    if let Some(attribute) = &options.reference {
+        let key = options
+            .rename
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| format!("{}_{}", ident, attribute.value()));
+        let expr = match inner_type(ty, "Vec") {
+            Some(_) => quote! {
+                ::nexus7::__private::hcl::Expression::Array(
+                    __field
+                        .iter()
+                        .map(|r| ::nexus7::__private::hcl::Expression::from(
+                            ::nexus7::Resource::attr(*r, #attribute),
+                        ))
+                        .collect(),
+                )
+            },
+            None => quote! { ::nexus7::Resource::attr(__field, #attribute) },
+        };
+        let add = quote! { __block = __block.add_attribute((#key, #expr)); };
+        return Ok(wrap_optional(ident, optional.is_some(), None, add));
+    }
+
+    let key = options
+        .rename
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_else(|| ident.to_string());
+    let add = quote! {
+        __block = __block.add_attribute((
+            #key,
+            ::nexus7::IntoExpression::into_expression(__field),
+        ));
+    };
+
+    if options.default.is_some() && optional.is_none() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`default` can only be used on `Option` fields",
+        ));
+    }
+
+    Ok(wrap_optional(
+        ident,
+        optional.is_some(),
+        options.default.as_ref(),
+        add,
+    ))
+}
+
+/// Binds the field to `__field`, unwrapping optional fields and skipping them when `None`
+/// unless a default is given.
+fn wrap_optional(
+    ident: &Ident,
+    optional: bool,
+    default: Option<&Expr>,
+    add: TokenStream2,
+) -> TokenStream2 {
+    match (optional, default) {
+        (true, Some(default)) => quote! {
+            let __field = __value.#ident.unwrap_or_else(|| #default);
+            #add
+        },
+        (true, None) => quote! {
+            if let ::core::option::Option::Some(__field) = __value.#ident {
+                #add
+            }
+        },
+        (false, _) => quote! {
+            let __field = __value.#ident;
+            #add
+        },
+    }
+}
+
+/// Returns `T` when `ty` is `wrapper<T>`, e.g. the inner type of an `Option`.
+fn inner_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
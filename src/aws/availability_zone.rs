@@ -1,24 +1,257 @@
+use crate::aws::region::Region;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::value::impl_value_from;
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Clone, Debug)]
-pub enum AvailabilityZone {
-    UsEast1a,
-    UsEast1b,
-    UsEast1c,
+/// Kind of zone, as reported by the `zone_type` attribute of `aws_availability_zone`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZoneType {
+    /// A regular availability zone, such as `us-east-1a`.
+    AvailabilityZone,
+    /// A Local Zone in a metro area, such as `us-west-2-lax-1a`.
+    LocalZone,
+    /// A Wavelength Zone in a carrier network, such as `us-east-1-wl1-bos-wlz-1`.
+    WavelengthZone,
 }
 
-impl fmt::Display for AvailabilityZone {
+impl fmt::Display for ZoneType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        match self {
+            ZoneType::AvailabilityZone => write!(f, "availability-zone"),
+            ZoneType::LocalZone => write!(f, "local-zone"),
+            ZoneType::WavelengthZone => write!(f, "wavelength-zone"),
+        }
     }
 }
 
+/// A zone name in a region, such as `us-east-1a`.
+///
+/// Zone names are mapped to physical zones per account; use [`AvailabilityZoneId`] to refer
+/// to the same zone across accounts.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AvailabilityZone {
+    region: Region,
+    zone_type: ZoneType,
+    suffix: String,
+}
+
 impl AvailabilityZone {
-    pub fn to_string(&self) -> String {
-        match self {
-            AvailabilityZone::UsEast1a => "us-east-1a".to_string(),
-            AvailabilityZone::UsEast1b => "us-east-1b".to_string(),
-            AvailabilityZone::UsEast1c => "us-east-1c".to_string(),
+    /// The availability zone `letter` of `region`, e.g. `b` for `us-east-1b`.
+    pub fn new(region: Region, letter: char) -> Result<Self, Error> {
+        if !letter.is_ascii_lowercase() {
+            return Err(Error::invalid(
+                "availability_zone",
+                "letter",
+                format!("`{}` is not a lowercase letter", letter),
+            ));
+        }
+
+        Ok(AvailabilityZone {
+            region,
+            zone_type: ZoneType::AvailabilityZone,
+            suffix: letter.to_string(),
+        })
+    }
+
+    /// A Local Zone of `region`, given by its location such as `lax-1a`.
+    pub fn local(region: Region, location: &str) -> Result<Self, Error> {
+        Self::extended(region, ZoneType::LocalZone, location)
+    }
+
+    /// A Wavelength Zone of `region`, given by its location such as `wl1-bos-wlz-1`.
+    pub fn wavelength(region: Region, location: &str) -> Result<Self, Error> {
+        Self::extended(region, ZoneType::WavelengthZone, location)
+    }
+
+    fn extended(region: Region, zone_type: ZoneType, location: &str) -> Result<Self, Error> {
+        if !is_location(location) {
+            return Err(Error::invalid(
+                "availability_zone",
+                "location",
+                format!("`{}` is not a valid zone location", location),
+            ));
+        }
+
+        Ok(AvailabilityZone {
+            region,
+            zone_type,
+            suffix: format!("-{}", location),
+        })
+    }
+
+    /// Region the zone belongs to.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Whether this is a regular, Local or Wavelength zone.
+    pub fn zone_type(&self) -> ZoneType {
+        self.zone_type
+    }
+
+    /// The part of the name after the region code, such as `a` or `lax-1a`.
+    pub fn suffix(&self) -> &str {
+        self.suffix.trim_start_matches('-')
+    }
+}
+
+impl FromStr for AvailabilityZone {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::invalid(
+                "availability_zone",
+                "name",
+                format!("`{}` is not a valid zone name", name),
+            )
+        };
+
+        let region = Region::ALL
+            .iter()
+            .filter(|region| name.starts_with(region.as_str()))
+            .max_by_key(|region| region.as_str().len())
+            .ok_or_else(invalid)?;
+        let rest = &name[region.as_str().len()..];
+
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) => Self::new(*region, letter),
+            (Some('-'), Some(_)) if rest.starts_with("-wl") => {
+                Self::wavelength(*region, &rest[1..])
+            }
+            (Some('-'), Some(_)) => Self::local(*region, &rest[1..]),
+            _ => Err(invalid()),
+        }
+        .map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for AvailabilityZone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.region, self.suffix)
+    }
+}
+
+impl IntoExpression for AvailabilityZone {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(AvailabilityZone);
+
+/// An account-independent zone ID, such as `use1-az1` or `usw2-lax1-az1`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AvailabilityZoneId {
+    region: Region,
+    suffix: String,
+}
+
+impl AvailabilityZoneId {
+    /// The `number`th availability zone ID of `region`, e.g. `use1-az4`.
+    pub fn new(region: Region, number: u8) -> Self {
+        AvailabilityZoneId {
+            region,
+            suffix: format!("az{}", number),
+        }
+    }
+
+    /// Region the zone belongs to.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+}
+
+impl FromStr for AvailabilityZoneId {
+    type Err = Error;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let (prefix, suffix) = id.split_once('-').unwrap_or((id, ""));
+
+        match Region::ALL.iter().find(|r| r.zone_id_prefix() == prefix) {
+            Some(region) if is_location(suffix) => Ok(AvailabilityZoneId {
+                region: *region,
+                suffix: suffix.to_string(),
+            }),
+            _ => Err(Error::invalid(
+                "availability_zone_id",
+                "id",
+                format!("`{}` is not a valid zone ID", id),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AvailabilityZoneId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.region.zone_id_prefix(), self.suffix)
+    }
+}
+
+impl IntoExpression for AvailabilityZoneId {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(AvailabilityZoneId);
+
+fn is_location(location: &str) -> bool {
+    !location.is_empty()
+        && !location.starts_with('-')
+        && !location.ends_with('-')
+        && location
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_availability_zone_names() {
+        let zone = AvailabilityZone::new(Region::UsEast1, 'b').unwrap();
+        assert_eq!(zone.to_string(), "us-east-1b");
+
+        let zone: AvailabilityZone = "us-gov-west-1a".parse().unwrap();
+        assert_eq!(zone.region(), Region::UsGovWest1);
+        assert_eq!(zone.zone_type(), ZoneType::AvailabilityZone);
+
+        let zone: AvailabilityZone = "us-west-2-lax-1a".parse().unwrap();
+        assert_eq!(zone.region(), Region::UsWest2);
+        assert_eq!(zone.zone_type(), ZoneType::LocalZone);
+        assert_eq!(zone.to_string(), "us-west-2-lax-1a");
+
+        let zone: AvailabilityZone = "us-east-1-wl1-bos-wlz-1".parse().unwrap();
+        assert_eq!(zone.zone_type(), ZoneType::WavelengthZone);
+        assert_eq!(zone.zone_type().to_string(), "wavelength-zone");
+    }
+
+    #[test]
+    fn test_invalid_availability_zone_names() {
+        for name in ["us-east-1", "us-east-1B", "us-east-9a", "us-east-1-", "eu"] {
+            assert!(name.parse::<AvailabilityZone>().is_err(), "{}", name);
         }
     }
+
+    #[test]
+    fn test_availability_zone_ids() {
+        assert_eq!(
+            AvailabilityZoneId::new(Region::ApNortheast1, 4).to_string(),
+            "apne1-az4"
+        );
+
+        let id: AvailabilityZoneId = "usw2-lax1-az1".parse().unwrap();
+        assert_eq!(id.region(), Region::UsWest2);
+
+        let id: AvailabilityZoneId = "usge1-az2".parse().unwrap();
+        assert_eq!(id.region(), Region::UsGovEast1);
+
+        assert!("xyz1-az1".parse::<AvailabilityZoneId>().is_err());
+        assert!("use1".parse::<AvailabilityZoneId>().is_err());
+    }
 }
@@ -0,0 +1,172 @@
+use crate::aws::region::Region;
+use crate::aws::storage::dynamodb::Table;
+use crate::aws::storage::s3::Bucket;
+use crate::backend::Backend;
+use crate::error::Error;
+use hcl::{Block, Expression};
+
+/// Stores state as a key in an S3 bucket, with optional DynamoDB or S3 lockfile locking.
+///
+/// Backend blocks cannot contain references, so every value is written as a literal.
+#[derive(Debug, Clone)]
+pub struct S3 {
+    /// Name of the S3 bucket.
+    pub bucket: String,
+
+    /// Path to the state file inside the bucket.
+    pub key: String,
+
+    /// Region of the bucket.
+    pub region: Region,
+
+    /// Name of the DynamoDB table used for state locking.
+    pub dynamodb_table: Option<String>,
+
+    /// Whether to use an S3 lockfile for state locking.
+    pub use_lockfile: Option<bool>,
+
+    /// Enable server side encryption of the state file.
+    pub encrypt: Option<bool>,
+
+    /// ARN of the KMS key used to encrypt the state file.
+    pub kms_key_id: Option<String>,
+}
+
+impl S3 {
+    /// Points the backend at a bucket defined in a bootstrap stack. The bucket must have an
+    /// explicit name, since generated names are only known after apply.
+    pub fn from_bucket(bucket: &Bucket, key: &str, region: Region) -> Result<Self, Error> {
+        let name = bucket.name.clone().ok_or(Error::MissingArgument {
+            resource: "backend.s3",
+            argument: "bucket",
+        })?;
+
+        Ok(S3 {
+            bucket: name,
+            key: key.to_string(),
+            region,
+            dynamodb_table: None,
+            use_lockfile: None,
+            encrypt: Some(true),
+            kms_key_id: None,
+        })
+    }
+
+    /// Locks state with a DynamoDB table defined in a bootstrap stack.
+    pub fn with_lock_table(mut self, table: &Table) -> Self {
+        self.dynamodb_table = Some(table.name.clone());
+        self
+    }
+}
+
+impl From<S3> for Backend {
+    fn from(s3: S3) -> Self {
+        Backend::S3(s3)
+    }
+}
+
+impl From<S3> for Block {
+    fn from(s3: S3) -> Self {
+        let mut block = Block::builder("backend")
+            .add_label("s3")
+            .add_attribute(("bucket", Expression::String(s3.bucket)))
+            .add_attribute(("key", Expression::String(s3.key)))
+            .add_attribute(("region", Expression::from(s3.region)));
+
+        if let Some(dynamodb_table) = s3.dynamodb_table {
+            block = block.add_attribute(("dynamodb_table", Expression::String(dynamodb_table)));
+        }
+
+        if let Some(use_lockfile) = s3.use_lockfile {
+            block = block.add_attribute(("use_lockfile", Expression::Bool(use_lockfile)));
+        }
+
+        if let Some(encrypt) = s3.encrypt {
+            block = block.add_attribute(("encrypt", Expression::Bool(encrypt)));
+        }
+
+        if let Some(kms_key_id) = s3.kms_key_id {
+            block = block.add_attribute(("kms_key_id", Expression::String(kms_key_id)));
+        }
+
+        block.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::MetaArguments;
+    use crate::terraform::TerraformSettings;
+
+    fn state_bucket(name: Option<&str>) -> Bucket {
+        Bucket {
+            name: name.map(str::to_string),
+            acl: None,
+            prefix: None,
+            force_destroy: None,
+            object_lock_enabled: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    #[test]
+    fn test_s3_backend_from_bootstrap_resources() {
+        let bucket = state_bucket(Some("acme-terraform-state"));
+        let table = Table::state_lock("terraform-locks");
+
+        let backend = S3::from_bucket(&bucket, "network/terraform.tfstate", Region::UsEast1)
+            .unwrap()
+            .with_lock_table(&table);
+
+        let settings = TerraformSettings {
+            backend: Some(backend.into()),
+            ..Default::default()
+        };
+        let block: Block = settings.into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"backend "s3" {"#));
+        assert!(hcl.contains(r#"bucket = "acme-terraform-state""#));
+        assert!(hcl.contains(r#"key = "network/terraform.tfstate""#));
+        assert!(hcl.contains(r#"region = "us-east-1""#));
+        assert!(hcl.contains(r#"dynamodb_table = "terraform-locks""#));
+        assert!(hcl.contains("encrypt = true"));
+    }
+
+    #[test]
+    fn test_s3_backend_requires_named_bucket() {
+        let bucket = Bucket {
+            prefix: Some("state-".to_string()),
+            ..state_bucket(None)
+        };
+
+        assert!(matches!(
+            S3::from_bucket(&bucket, "terraform.tfstate", Region::UsEast1),
+            Err(Error::MissingArgument {
+                argument: "bucket",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_s3_backend_with_lockfile() {
+        let block: Block = S3 {
+            bucket: "acme-terraform-state".to_string(),
+            key: "terraform.tfstate".to_string(),
+            region: Region::UsWest2,
+            dynamodb_table: None,
+            use_lockfile: Some(true),
+            encrypt: Some(true),
+            kms_key_id: Some("arn:aws:kms:us-west-2:111122223333:key/abcd".to_string()),
+        }
+        .into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains("use_lockfile = true"));
+        assert!(hcl.contains(r#"kms_key_id = "arn:aws:kms:us-west-2:111122223333:key/abcd""#));
+        assert!(!hcl.contains("dynamodb_table"));
+    }
+}
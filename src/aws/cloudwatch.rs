@@ -0,0 +1,103 @@
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use std::collections::HashMap;
+
+/// Retention periods accepted by CloudWatch Logs, in days. Zero keeps events forever.
+const RETENTION_DAYS: [u32; 23] = [
+    0, 1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1096, 1827, 2192, 2557, 2922,
+    3288, 3653,
+];
+
+/// Represents an AWS CloudWatch Logs log group.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_cloudwatch_log_group")]
+pub struct LogGroup {
+    /// The name of the log group resource.
+    #[hcl(label)]
+    pub name: String,
+    /// Name of the log group in CloudWatch Logs, such as `/aws/vpc/flow-logs`.
+    #[hcl(rename = "name")]
+    pub log_group_name: Value<String>,
+    /// Number of days to keep log events.
+    pub retention_in_days: Option<Value<u32>>,
+    /// ARN of the KMS key used to encrypt log data.
+    pub kms_key_id: Option<Value<String>>,
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl LogGroup {
+    /// Checks the log group arguments against the constraints enforced by CloudWatch Logs.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if let Some(name) = self.log_group_name.as_literal() {
+            if name.is_empty() || name.len() > 512 {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "name",
+                    "must be between 1 and 512 characters",
+                ));
+            }
+        }
+
+        if let Some(days) = self.retention_in_days.as_ref().and_then(Value::as_literal) {
+            if !RETENTION_DAYS.contains(days) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "retention_in_days",
+                    format!("{} is not a supported retention period", days),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// ARN of the log group.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hcl::Block;
+
+    #[test]
+    fn test_log_group_to_hcl() {
+        let log_group = LogGroup {
+            name: "flow_logs".to_string(),
+            log_group_name: "/aws/vpc/flow-logs".into(),
+            retention_in_days: Some(90.into()),
+            kms_key_id: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(log_group.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_cloudwatch_log_group" "flow_logs""#));
+        assert!(hcl.contains(r#"name = "/aws/vpc/flow-logs""#));
+        assert!(hcl.contains("retention_in_days = 90"));
+
+        let unsupported = LogGroup {
+            retention_in_days: Some(45.into()),
+            ..log_group
+        };
+        assert!(matches!(
+            Block::try_from(unsupported),
+            Err(Error::InvalidArgument {
+                argument: "retention_in_days",
+                ..
+            })
+        ));
+    }
+}
@@ -1,9 +1,24 @@
 use crate::aws::iam;
+use crate::aws::network::security_group::SecurityGroup;
 use crate::aws::network::subnet::Subnet;
 use crate::aws::network::vpc::Vpc;
-use hcl::{Block, Expression, ObjectKey};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::output::Output;
+use crate::reference::{Reference, Resource};
+use crate::value::Value;
+use hcl::{Block, Expression, Identifier};
 use std::collections::HashMap;
 
+/// Control plane log types accepted by `enabled_cluster_log_types`.
+const LOG_TYPES: [&str; 5] = [
+    "api",
+    "audit",
+    "authenticator",
+    "controllerManager",
+    "scheduler",
+];
+
 /// Represents an AWS EKS Cluster resource.
 #[derive(Debug, Clone)]
 pub struct Cluster<'a> {
@@ -13,97 +28,209 @@ pub struct Cluster<'a> {
     pub vpc: &'a Vpc,
     /// List of subnet IDs where the EKS cluster will be created.
     pub subnet_ids: Vec<&'a Subnet<'a>>,
+    /// Security groups for the network interfaces EKS creates for the control plane.
+    pub security_groups: Vec<&'a SecurityGroup<'a>>,
     /// Desired Kubernetes master version.
-    pub version: Option<String>,
+    pub version: Option<Value<String>>,
     /// Role to use to access other AWS services.
     pub role: &'a iam::Role,
     /// Desired Kubernetes version for the cluster.
-    pub kubernetes_version: Option<String>,
+    pub kubernetes_version: Option<Value<String>>,
     /// Indicates whether or not the Amazon EKS private API server endpoint is enabled.
-    pub enabled_cluster_log_types: Option<Vec<String>>,
+    pub enabled_cluster_log_types: Option<Value<Vec<String>>>,
     /// List of the desired control plane logging to enable.
-    pub endpoint_private_access: Option<bool>,
+    pub endpoint_private_access: Option<Value<bool>>,
     /// Indicates whether or not the Amazon EKS public API server endpoint is enabled.
-    pub endpoint_public_access: Option<bool>,
+    pub endpoint_public_access: Option<Value<bool>>,
     /// Configuration block with encryption configuration for the cluster.
     pub encryption_config: Option<EncryptionConfig>,
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    pub meta: MetaArguments,
 }
 
 /// Represents the encryption configuration for an EKS cluster.
 #[derive(Debug, Clone)]
 pub struct EncryptionConfig {
     /// KMS key ARN to use for envelope encryption of Kubernetes secrets.
-    pub kms_key_arn: String,
+    pub kms_key_arn: Value<String>,
+}
+
+impl Resource for Cluster<'_> {
+    const TYPE: &'static str = "aws_eks_cluster";
+
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
 
-impl<'a> From<Cluster<'a>> for Block {
-    fn from(cluster: Cluster<'a>) -> Self {
+impl Cluster<'_> {
+    /// Checks the cluster arguments against the constraints enforced by EKS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if self.subnet_ids.len() < 2 {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "subnet_ids",
+                "at least two subnets in different availability zones are required",
+            ));
+        }
+
+        if let Some(log_types) = self
+            .enabled_cluster_log_types
+            .as_ref()
+            .and_then(Value::as_literal)
+        {
+            if let Some(log_type) = log_types.iter().find(|t| !LOG_TYPES.contains(&t.as_str())) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "enabled_cluster_log_types",
+                    format!("unknown log type `{}`", log_type),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Name of the cluster.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// ARN of the cluster.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// Endpoint for your Kubernetes API server.
+    pub fn endpoint(&self) -> Reference {
+        self.attr("endpoint")
+    }
+
+    /// Base64 encoded certificate data required to communicate with your cluster.
+    pub fn certificate_authority_data(&self) -> Reference {
+        self.attr("certificate_authority").index(0).attr("data")
+    }
+
+    /// Issuer URL for the OpenID Connect identity provider.
+    pub fn oidc_issuer(&self) -> Reference {
+        self.attr("identity")
+            .index(0)
+            .attr("oidc")
+            .index(0)
+            .attr("issuer")
+    }
+
+    /// Cluster security group that was created by Amazon EKS for the cluster.
+    pub fn cluster_security_group_id(&self) -> Reference {
+        self.attr("vpc_config")
+            .index(0)
+            .attr("cluster_security_group_id")
+    }
+
+    /// Platform version for the cluster.
+    pub fn platform_version(&self) -> Reference {
+        self.attr("platform_version")
+    }
+
+    /// Status of the EKS cluster. One of `CREATING`, `ACTIVE`, `DELETING`, `FAILED`.
+    pub fn status(&self) -> Reference {
+        self.attr("status")
+    }
+
+    /// Outputs for connecting to the cluster, named `<name>_cluster_<attribute>`: its ID,
+    /// ARN, API endpoint, certificate authority data and OIDC issuer URL.
+    pub fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::new(format!("{}_cluster_id", self.name), self.id()),
+            Output::new(format!("{}_cluster_arn", self.name), self.arn()),
+            Output::new(format!("{}_cluster_endpoint", self.name), self.endpoint()),
+            Output::new(
+                format!("{}_cluster_certificate_authority_data", self.name),
+                self.certificate_authority_data(),
+            ),
+            Output::new(
+                format!("{}_cluster_oidc_issuer", self.name),
+                self.oidc_issuer(),
+            ),
+        ]
+    }
+}
+
+impl<'a> TryFrom<Cluster<'a>> for Block {
+    type Error = Error;
+
+    fn try_from(cluster: Cluster<'a>) -> Result<Self, Self::Error> {
+        cluster.validate()?;
+
         let mut block = Block::builder("resource")
-            .add_label("aws_eks_cluster")
-            .add_label(&cluster.name)
+            .add_label(Cluster::TYPE)
+            .add_label(Identifier::sanitized(&cluster.name).into_inner())
             .add_attribute(("name", Expression::String(cluster.name)))
-            .add_attribute(("role_arn", Expression::String(cluster.role.arn.to_string())));
+            .add_attribute(("role_arn", cluster.role.arn()));
 
-        let vpc_config = Block::builder("vpc_config")
-            .add_attribute((
-                "subnet_ids",
+        let mut vpc_config = Block::builder("vpc_config").add_attribute((
+            "subnet_ids",
+            Expression::Array(
+                cluster
+                    .subnet_ids
+                    .iter()
+                    .map(|s| Expression::from(s.id()))
+                    .collect(),
+            ),
+        ));
+
+        if !cluster.security_groups.is_empty() {
+            vpc_config = vpc_config.add_attribute((
+                "security_group_ids",
                 Expression::Array(
                     cluster
-                        .subnet_ids
+                        .security_groups
                         .iter()
-                        .map(|s| Expression::from(format!("${{aws_subnet.{}.id}}", s.name)))
+                        .map(|g| Expression::from(g.id()))
                         .collect(),
                 ),
-            ))
-            .build();
+            ));
+        }
 
-        block = block.add_block(vpc_config);
+        block = block.add_block(vpc_config.build());
 
         if let Some(version) = cluster.version {
-            block = block.add_attribute(("version", Expression::String(version)));
+            block = block.add_attribute(("version", version));
         }
 
         if let Some(kubernetes_version) = cluster.kubernetes_version {
-            block =
-                block.add_attribute(("kubernetes_version", Expression::String(kubernetes_version)));
+            block = block.add_attribute(("kubernetes_version", kubernetes_version));
         }
 
         if let Some(enabled_cluster_log_types) = cluster.enabled_cluster_log_types {
-            block = block.add_attribute((
-                "enabled_cluster_log_types",
-                Expression::Array(
-                    enabled_cluster_log_types
-                        .into_iter()
-                        .map(Expression::String)
-                        .collect(),
-                ),
-            ));
+            block = block.add_attribute(("enabled_cluster_log_types", enabled_cluster_log_types));
         }
 
         if let Some(endpoint_private_access) = cluster.endpoint_private_access {
-            block = block.add_attribute((
-                "endpoint_private_access",
-                Expression::Bool(endpoint_private_access),
-            ));
+            block = block.add_attribute(("endpoint_private_access", endpoint_private_access));
         }
 
         if let Some(endpoint_public_access) = cluster.endpoint_public_access {
-            block = block.add_attribute((
-                "endpoint_public_access",
-                Expression::Bool(endpoint_public_access),
-            ));
+            block = block.add_attribute(("endpoint_public_access", endpoint_public_access));
         }
 
         if let Some(encryption_config) = cluster.encryption_config {
             let encryption_block = Block::builder("encryption_config")
                 .add_block(
                     Block::builder("provider")
-                        .add_attribute((
-                            "key_arn",
-                            Expression::String(encryption_config.kms_key_arn),
-                        ))
+                        .add_attribute(("key_arn", encryption_config.kms_key_arn))
                         .build(),
                 )
                 .build();
@@ -111,85 +238,138 @@ impl<'a> From<Cluster<'a>> for Block {
         }
 
         if let Some(tags) = cluster.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
+            block = block.add_attribute(("tags", tags));
         }
 
-        block.build()
+        block = cluster.meta.apply(block);
+
+        Ok(block.build())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::variable::{Type, Variable};
 
     #[test]
     fn test_eks_cluster_to_hcl() {
-        let vpc = Vpc {
-            name: "test-vpc".to_string(),
-            cidr_block: "10.0.0.0/16".to_string(),
-            instance_tenancy: None,
-            enable_dns_hostnames: None,
-            enable_dns_support: None,
-            enable_classiclink: None,
-            enable_classiclink_dns_support: None,
-            assign_generated_ipv6_cidr_block: None,
-            tags: None,
-        };
+        let vpc = Vpc::new("test-vpc", "10.0.0.0/16".parse().unwrap());
 
-        let subnet1 = Subnet {
-            name: "subnet1".to_string(),
-            vpc: &vpc,
-            cidr_block: "10.0.1.0/24".to_string(),
-            availability_zone: None,
-            map_public_ip_on_launch: None,
+        let subnet1 = Subnet::new("subnet1", &vpc, "10.0.1.0/24".parse().unwrap());
+
+        let subnet2 = Subnet::new("subnet2", &vpc, "10.0.2.0/24".parse().unwrap());
+
+        let role = iam::Role {
+            name: "eks-cluster-role".to_string(),
+            assume_role_policy: "{}".into(),
+            description: None,
+            path: None,
+            max_session_duration: None,
+            permissions_boundary: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
 
-        let subnet2 = Subnet {
-            name: "subnet2".to_string(),
+        let control_plane = SecurityGroup {
+            name: "control-plane".to_string(),
+            description: None,
             vpc: &vpc,
-            cidr_block: "10.0.2.0/24".to_string(),
-            availability_zone: None,
-            map_public_ip_on_launch: None,
+            revoke_rules_on_delete: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
 
         let cluster = Cluster {
             name: "test-cluster".to_string(),
             vpc: &vpc,
             subnet_ids: vec![&subnet1, &subnet2],
-            version: Some("1.21".to_string()),
-            role_arn: "arn:aws:iam::123456789012:role/eks-cluster-role".to_string(),
+            security_groups: vec![&control_plane],
+            version: Some("1.21".into()),
+            role: &role,
             kubernetes_version: None,
-            enabled_cluster_log_types: Some(vec!["api".to_string(), "audit".to_string()]),
-            endpoint_private_access: Some(true),
-            endpoint_public_access: Some(false),
+            enabled_cluster_log_types: Some(vec!["api".to_string(), "audit".to_string()].into()),
+            endpoint_private_access: Some(true.into()),
+            endpoint_public_access: Some(false.into()),
             encryption_config: Some(EncryptionConfig {
                 kms_key_arn:
                     "arn:aws:kms:us-west-2:111122223333:key/1234abcd-12ab-34cd-56ef-1234567890ab"
-                        .to_string(),
+                        .to_string()
+                        .into(),
             }),
-            tags: Some(HashMap::from([
-                ("Environment".to_string(), "Production".to_string()),
-                ("Project".to_string(), "EKS".to_string()),
-            ])),
+            tags: Some(
+                HashMap::from([
+                    ("Environment".to_string(), "Production".to_string()),
+                    ("Project".to_string(), "EKS".to_string()),
+                ])
+                .into(),
+            ),
+            meta: MetaArguments::default(),
+        };
+
+        let cluster_version = Variable {
+            name: "cluster_version".to_string(),
+            kind: Some(Type::String),
+            default: Some("1.29".into()),
+            description: None,
+            sensitive: None,
+            nullable: None,
+            validation: vec![],
+        };
+        let versioned = Cluster {
+            version: Some(cluster_version.reference().into()),
+            ..cluster.clone()
+        };
+        let hcl = hcl::to_string(&Block::try_from(versioned).unwrap()).unwrap();
+        assert!(hcl.contains("version = var.cluster_version"));
+
+        let outputs: Vec<String> = cluster
+            .outputs()
+            .into_iter()
+            .map(|o| hcl::to_string(&Block::try_from(o).unwrap()).unwrap())
+            .collect();
+        assert!(outputs
+            .iter()
+            .any(|o| o.contains(r#"output "test-cluster_cluster_endpoint""#)
+                && o.contains("value = aws_eks_cluster.test-cluster.endpoint")));
+
+        let single_subnet = Cluster {
+            subnet_ids: vec![&subnet1],
+            ..cluster.clone()
         };
+        assert!(matches!(
+            Block::try_from(single_subnet),
+            Err(Error::InvalidArgument {
+                argument: "subnet_ids",
+                ..
+            })
+        ));
 
-        let block: Block = cluster.into();
+        let block = Block::try_from(cluster).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
 
         assert!(hcl.contains(r#"resource "aws_eks_cluster" "test-cluster" {"#));
         assert!(hcl.contains(r#"name = "test-cluster""#));
-        assert!(hcl.contains(r#"role_arn = "arn:aws:iam::123456789012:role/eks-cluster-role""#));
+        assert!(hcl.contains(r#"role_arn = aws_iam_role.eks-cluster-role.arn"#));
         assert!(hcl.contains(r#"vpc_config {"#));
-        assert!(hcl.contains(r#"subnet_ids = [aws_subnet.subnet1.id, aws_subnet.subnet2.id]"#));
+        assert!(hcl.contains(
+            r#"subnet_ids = [
+      aws_subnet.subnet1.id,
+      aws_subnet.subnet2.id
+    ]"#
+        ));
+        assert!(hcl.contains(
+            r#"security_group_ids = [
+      aws_security_group.control-plane.id
+    ]"#
+        ));
         assert!(hcl.contains(r#"version = "1.21""#));
-        assert!(hcl.contains(r#"enabled_cluster_log_types = ["api", "audit"]"#));
+        assert!(hcl.contains(
+            r#"enabled_cluster_log_types = [
+    "api",
+    "audit"
+  ]"#
+        ));
         assert!(hcl.contains(r#"endpoint_private_access = true"#));
         assert!(hcl.contains(r#"endpoint_public_access = false"#));
         assert!(hcl.contains(r#"encryption_config {"#));
@@ -1,3 +1,3 @@
 pub mod role;
 
-pub use role::Role;
+pub use role::{Role, RoleDataSource, RolePolicy};
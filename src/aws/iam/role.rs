@@ -1,8 +1,242 @@
-struct EncryptionConfig {
-    kms_key_arn: String,
-}
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use std::collections::HashMap;
 
-#[derive(Debug)]
+/// Represents an AWS IAM Role resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_iam_role")]
 pub struct Role {
-    pub arn: String,
+    /// Friendly name of the role.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+    /// Policy that grants an entity permission to assume the role, as a JSON document.
+    pub assume_role_policy: Value<String>,
+    /// Description of the role.
+    pub description: Option<Value<String>>,
+    /// Path to the role.
+    pub path: Option<Value<String>>,
+    /// Maximum session duration (in seconds) that you want to set for the specified role.
+    pub max_session_duration: Option<Value<u32>>,
+    /// ARN of the policy that is used to set the permissions boundary for the role.
+    pub permissions_boundary: Option<Value<String>>,
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl Role {
+    /// Checks the role arguments against the constraints enforced by IAM.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if self.name.len() > 64 {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "name",
+                "must be at most 64 characters",
+            ));
+        }
+
+        if self
+            .assume_role_policy
+            .as_literal()
+            .is_some_and(String::is_empty)
+        {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "assume_role_policy",
+            });
+        }
+
+        if let Some(duration) = self
+            .max_session_duration
+            .as_ref()
+            .and_then(Value::as_literal)
+        {
+            if !(3600..=43200).contains(duration) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "max_session_duration",
+                    "must be between 3600 and 43200 seconds",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Amazon Resource Name (ARN) specifying the role.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// Name of the role.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// Stable and unique string identifying the role.
+    pub fn unique_id(&self) -> Reference {
+        self.attr("unique_id")
+    }
+
+    /// Creation date of the IAM role.
+    pub fn create_date(&self) -> Reference {
+        self.attr("create_date")
+    }
+}
+
+/// Represents an inline policy embedded in an AWS IAM Role.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_iam_role_policy")]
+pub struct RolePolicy<'a> {
+    /// Name of the policy, unique within the role.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+    /// Role the policy is embedded in.
+    #[hcl(reference = "id", rename = "role")]
+    pub role: &'a Role,
+    /// Policy document, as a JSON document.
+    pub policy: Value<String>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl RolePolicy<'_> {
+    /// Checks the policy arguments against the constraints enforced by IAM.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() || self.name.len() > 128 {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "name",
+                "must be between 1 and 128 characters",
+            ));
+        }
+
+        if self.policy.as_literal().is_some_and(String::is_empty) {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "policy",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Role name and policy name, separated by a colon.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Represents a data source for an existing AWS IAM Role.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_iam_role")]
+pub struct RoleDataSource {
+    /// Friendly IAM role name to match.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+}
+
+impl RoleDataSource {
+    /// Amazon Resource Name (ARN) specifying the role.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// Stable and unique string identifying the role.
+    pub fn unique_id(&self) -> Reference {
+        self.attr("unique_id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hcl::Block;
+
+    #[test]
+    fn test_role_to_hcl() {
+        let role = Role {
+            name: "eks-cluster".to_string(),
+            assume_role_policy: r#"{"Version":"2012-10-17"}"#.into(),
+            description: Some("EKS control plane".into()),
+            path: None,
+            max_session_duration: Some(3600.into()),
+            permissions_boundary: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        assert_eq!(role.arn().to_string(), "aws_iam_role.eks-cluster.arn");
+
+        let block = Block::try_from(role).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_iam_role" "eks-cluster" {"#));
+        assert!(hcl.contains(r#"name = "eks-cluster""#));
+        assert!(hcl.contains(r#"description = "EKS control plane""#));
+        assert!(hcl.contains(r#"max_session_duration = 3600"#));
+    }
+
+    #[test]
+    fn test_role_policy_to_hcl() {
+        let role = Role {
+            name: "flow-logs".to_string(),
+            assume_role_policy: r#"{"Version":"2012-10-17"}"#.into(),
+            description: None,
+            path: None,
+            max_session_duration: None,
+            permissions_boundary: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let policy = RolePolicy {
+            name: "write-logs".to_string(),
+            role: &role,
+            policy: r#"{"Version":"2012-10-17","Statement":[]}"#.into(),
+            meta: MetaArguments::default(),
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(policy).unwrap()).unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_iam_role_policy" "write-logs" {"#));
+        assert!(hcl.contains(r#"name = "write-logs""#));
+        assert!(hcl.contains("role = aws_iam_role.flow-logs.id"));
+    }
+
+    #[test]
+    fn test_role_data_source_to_hcl() {
+        let data_source = RoleDataSource {
+            name: "existing".to_string(),
+        };
+
+        assert_eq!(
+            data_source.arn().to_string(),
+            "data.aws_iam_role.existing.arn"
+        );
+
+        let block: Block = data_source.into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"data "aws_iam_role" "existing" {"#));
+        assert!(hcl.contains(r#"name = "existing""#));
+    }
 }
@@ -1,4 +1,6 @@
 pub mod availability_zone;
+pub mod backend;
+pub mod cloudwatch;
 pub mod eks;
 pub mod iam;
 pub mod network;
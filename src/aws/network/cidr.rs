@@ -1,5 +1,31 @@
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::value::impl_value_from;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// Addresses AWS reserves in every subnet: the network address, the VPC router, the DNS
+/// server, one for future use and the broadcast address.
+pub const AWS_RESERVED_ADDRESSES: u64 = 5;
+
+/// Splits `a.b.c.d/n` into its address and prefix length.
+fn split_cidr<A: FromStr>(cidr: &str) -> Result<(A, u8), Error> {
+    let invalid = || {
+        Error::invalid(
+            "cidr_block",
+            "cidr_block",
+            format!("`{}` is not a valid CIDR block", cidr),
+        )
+    };
+
+    let (address, prefix_length) = cidr.split_once('/').ok_or_else(invalid)?;
+    Ok((
+        address.parse().map_err(|_| invalid())?,
+        prefix_length.parse().map_err(|_| invalid())?,
+    ))
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Block {
@@ -8,9 +34,12 @@ pub struct Block {
 }
 
 impl Block {
-    pub fn new(address: Ipv4Addr, prefix_length: u8) -> Result<Self, String> {
+    pub fn new(address: Ipv4Addr, prefix_length: u8) -> Result<Self, Error> {
         if prefix_length > 32 {
-            return Err("Prefix length must be between 0 and 32".to_string());
+            return Err(Error::InvalidPrefixLength {
+                prefix_length,
+                max: 32,
+            });
         }
         Ok(Block {
             address,
@@ -52,14 +81,260 @@ impl Block {
         let ip = u32::from(ip);
         ip >= network && ip <= broadcast
     }
+
+    /// Whether the two blocks share any address.
+    pub fn overlaps(&self, other: &Block) -> bool {
+        self.contains(other.network_address()) || other.contains(self.network_address())
+    }
+
+    /// The same block with its host bits cleared, e.g. `10.0.1.7/24` becomes `10.0.1.0/24`.
+    pub fn normalized(&self) -> Block {
+        Block {
+            address: self.network_address(),
+            prefix_length: self.prefix_length,
+        }
+    }
+
+    /// Number of addresses in the block.
+    pub fn size(&self) -> u64 {
+        1 << (32 - u32::from(self.prefix_length))
+    }
+
+    /// Number of addresses left for hosts once AWS takes its
+    /// [`AWS_RESERVED_ADDRESSES`], which is zero for blocks too small to be a subnet.
+    pub fn aws_usable_hosts(&self) -> u64 {
+        self.size().saturating_sub(AWS_RESERVED_ADDRESSES)
+    }
+
+    /// Whether every address of this block is inside `other`.
+    pub fn is_subnet_of(&self, other: &Block) -> bool {
+        self.prefix_length >= other.prefix_length && other.contains(self.network_address())
+    }
+
+    /// The block one bit shorter that contains this one, or `None` for `/0`.
+    pub fn supernet(&self) -> Option<Block> {
+        let prefix_length = self.prefix_length.checked_sub(1)?;
+        Some(
+            Block {
+                address: self.address,
+                prefix_length,
+            }
+            .normalized(),
+        )
+    }
+
+    /// Splits the block into consecutive blocks of `new_prefix`, in address order.
+    pub fn subnets(&self, new_prefix: u8) -> Result<Subnets, Error> {
+        self.check_new_prefix(new_prefix)?;
+
+        let step = 1u64 << (32 - u32::from(new_prefix));
+        let start = u64::from(u32::from(self.network_address()));
+        Ok(Subnets {
+            next: start,
+            end: start + self.size(),
+            step,
+            prefix_length: new_prefix,
+        })
+    }
+
+    /// The `netnum`th block of `new_prefix` inside this one, counting from zero. This is
+    /// Terraform's `cidrsubnet(block, new_prefix - prefix_length, netnum)`.
+    pub fn nth_subnet(&self, new_prefix: u8, netnum: u64) -> Result<Block, Error> {
+        self.check_new_prefix(new_prefix)?;
+
+        let count = 1u64 << (new_prefix - self.prefix_length);
+        if netnum >= count {
+            return Err(Error::invalid(
+                "cidr_block",
+                "netnum",
+                format!(
+                    "{} only has {} /{} subnets, got index {}",
+                    self.normalized(),
+                    count,
+                    new_prefix,
+                    netnum
+                ),
+            ));
+        }
+
+        let step = 1u64 << (32 - u32::from(new_prefix));
+        let address = u64::from(u32::from(self.network_address())) + netnum * step;
+        Block::new(Ipv4Addr::from(address as u32), new_prefix)
+    }
+
+    fn check_new_prefix(&self, new_prefix: u8) -> Result<(), Error> {
+        if new_prefix > 32 {
+            return Err(Error::InvalidPrefixLength {
+                prefix_length: new_prefix,
+                max: 32,
+            });
+        }
+        if new_prefix < self.prefix_length {
+            return Err(Error::invalid(
+                "cidr_block",
+                "prefix_length",
+                format!("/{} is larger than {}", new_prefix, self.normalized()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Block {
+    type Err = Error;
+
+    fn from_str(cidr: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_length) = split_cidr(cidr)?;
+        Block::new(address, prefix_length)
+    }
 }
 
+impl TryFrom<&str> for Block {
+    type Error = Error;
+
+    fn try_from(cidr: &str) -> Result<Self, Self::Error> {
+        cidr.parse()
+    }
+}
+
+/// Serialized as its `a.b.c.d/n` string, as in state files.
+impl Serialize for Block {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Block {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cidr = String::deserialize(deserializer)?;
+        cidr.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Iterator over the equally sized blocks of a [`Block`], returned by [`Block::subnets`].
+#[derive(Debug, Clone)]
+pub struct Subnets {
+    next: u64,
+    end: u64,
+    step: u64,
+    prefix_length: u8,
+}
+
+impl Iterator for Subnets {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        if self.next >= self.end {
+            return None;
+        }
+        let block = Block {
+            address: Ipv4Addr::from(self.next as u32),
+            prefix_length: self.prefix_length,
+        };
+        self.next += self.step;
+        Some(block)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = ((self.end - self.next) / self.step) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Subnets {}
+
 impl fmt::Display for Block {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}/{}", self.address, self.prefix_length)
     }
 }
 
+impl IntoExpression for Block {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(Block);
+
+/// An IPv6 CIDR block, such as a VPC's `/56` or a subnet's `/64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ipv6Block {
+    address: Ipv6Addr,
+    prefix_length: u8,
+}
+
+impl Ipv6Block {
+    pub fn new(address: Ipv6Addr, prefix_length: u8) -> Result<Self, Error> {
+        if prefix_length > 128 {
+            return Err(Error::InvalidPrefixLength {
+                prefix_length,
+                max: 128,
+            });
+        }
+        Ok(Ipv6Block {
+            address,
+            prefix_length,
+        })
+    }
+
+    pub fn address(&self) -> Ipv6Addr {
+        self.address
+    }
+
+    pub fn prefix_length(&self) -> u8 {
+        self.prefix_length
+    }
+
+    fn host_mask(&self) -> u128 {
+        u128::MAX
+            .checked_shr(u32::from(self.prefix_length))
+            .unwrap_or(0)
+    }
+
+    pub fn network_address(&self) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.address) & !self.host_mask())
+    }
+
+    /// The highest address in the block. IPv6 has no broadcast address.
+    pub fn last_address(&self) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.address) | self.host_mask())
+    }
+
+    pub fn contains(&self, ip: Ipv6Addr) -> bool {
+        let ip = u128::from(ip);
+        ip >= u128::from(self.network_address()) && ip <= u128::from(self.last_address())
+    }
+
+    /// Whether the two blocks share any address.
+    pub fn overlaps(&self, other: &Ipv6Block) -> bool {
+        self.contains(other.network_address()) || other.contains(self.network_address())
+    }
+}
+
+impl FromStr for Ipv6Block {
+    type Err = Error;
+
+    fn from_str(cidr: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_length) = split_cidr(cidr)?;
+        Ipv6Block::new(address, prefix_length)
+    }
+}
+
+impl fmt::Display for Ipv6Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_length)
+    }
+}
+
+impl IntoExpression for Ipv6Block {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(Ipv6Block);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,7 +368,13 @@ mod tests {
     #[test]
     fn test_cidr_block_new_invalid_prefix() {
         let cidr = Block::new(Ipv4Addr::new(192, 168, 0, 0), 33);
-        assert!(cidr.is_err());
+        assert!(matches!(
+            cidr,
+            Err(Error::InvalidPrefixLength {
+                prefix_length: 33,
+                max: 32
+            })
+        ));
     }
 
     #[test]
@@ -269,9 +550,151 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cidr_block_overlaps() {
+        let vpc = Block::new(Ipv4Addr::new(10, 0, 0, 0), 16).unwrap();
+        let subnet = Block::new(Ipv4Addr::new(10, 0, 4, 0), 22).unwrap();
+        let other = Block::new(Ipv4Addr::new(10, 1, 0, 0), 16).unwrap();
+
+        assert!(vpc.overlaps(&subnet));
+        assert!(subnet.overlaps(&vpc));
+        assert!(!vpc.overlaps(&other));
+    }
+
     #[test]
     fn test_cidr_block_display_trait() {
         let cidr = Block::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap();
         assert_eq!(format!("{}", cidr), "192.168.0.0/16");
     }
+
+    #[test]
+    fn test_ipv6_block() {
+        let vpc = Ipv6Block::new("2600:1f18:4a3:6900::".parse().unwrap(), 56).unwrap();
+        let subnet = Ipv6Block::new("2600:1f18:4a3:6901::".parse().unwrap(), 64).unwrap();
+
+        assert_eq!(vpc.to_string(), "2600:1f18:4a3:6900::/56");
+        assert_eq!(
+            vpc.last_address(),
+            "2600:1f18:4a3:69ff:ffff:ffff:ffff:ffff"
+                .parse::<Ipv6Addr>()
+                .unwrap()
+        );
+        assert!(vpc.contains("2600:1f18:4a3:69ab::1".parse().unwrap()));
+        assert!(!vpc.contains("2600:1f18:4a3:6a00::".parse().unwrap()));
+        assert!(vpc.overlaps(&subnet));
+        assert!(subnet.overlaps(&vpc));
+    }
+
+    #[test]
+    fn test_ipv6_block_network_address() {
+        let cidr = Ipv6Block::new("2001:db8::1".parse().unwrap(), 32).unwrap();
+        assert_eq!(
+            cidr.network_address(),
+            "2001:db8::".parse::<Ipv6Addr>().unwrap()
+        );
+
+        let any = Ipv6Block::new(Ipv6Addr::UNSPECIFIED, 0).unwrap();
+        assert_eq!(any.to_string(), "::/0");
+        assert!(any.contains("2001:db8::1".parse().unwrap()));
+        assert!(matches!(
+            Ipv6Block::new(Ipv6Addr::UNSPECIFIED, 129),
+            Err(Error::InvalidPrefixLength { max: 128, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cidr_block_from_str() {
+        let cidr: Block = "10.0.1.7/24".parse().unwrap();
+        assert_eq!(cidr.address(), Ipv4Addr::new(10, 0, 1, 7));
+        assert_eq!(cidr.normalized().to_string(), "10.0.1.0/24");
+        assert_eq!(Block::try_from("10.0.0.0/16").unwrap().prefix_length(), 16);
+
+        for invalid in ["10.0.0.0", "10.0.0/16", "10.0.0.0/x", "::/0"] {
+            assert!(matches!(
+                invalid.parse::<Block>(),
+                Err(Error::InvalidArgument { .. })
+            ));
+        }
+        assert!(matches!(
+            "10.0.0.0/33".parse::<Block>(),
+            Err(Error::InvalidPrefixLength { .. })
+        ));
+
+        let ipv6: Ipv6Block = "2001:db8::/56".parse().unwrap();
+        assert_eq!(ipv6.prefix_length(), 56);
+    }
+
+    #[test]
+    fn test_cidr_block_subnets() {
+        let vpc: Block = "10.0.0.0/16".parse().unwrap();
+        let subnets: Vec<String> = vpc.subnets(18).unwrap().map(|b| b.to_string()).collect();
+        assert_eq!(
+            subnets,
+            [
+                "10.0.0.0/18",
+                "10.0.64.0/18",
+                "10.0.128.0/18",
+                "10.0.192.0/18"
+            ]
+        );
+        assert_eq!(vpc.subnets(24).unwrap().len(), 256);
+        assert_eq!(vpc.subnets(16).unwrap().count(), 1);
+        assert!(vpc.subnets(8).is_err());
+        assert!(vpc.subnets(33).is_err());
+
+        assert_eq!(vpc.nth_subnet(24, 3).unwrap().to_string(), "10.0.3.0/24");
+        assert_eq!(vpc.nth_subnet(20, 15).unwrap().to_string(), "10.0.240.0/20");
+        assert!(matches!(
+            vpc.nth_subnet(20, 16),
+            Err(Error::InvalidArgument {
+                argument: "netnum",
+                ..
+            })
+        ));
+
+        let everything: Block = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(everything.subnets(1).unwrap().len(), 2);
+        assert_eq!(
+            everything.nth_subnet(1, 1).unwrap().to_string(),
+            "128.0.0.0/1"
+        );
+    }
+
+    #[test]
+    fn test_cidr_block_relations_and_sizes() {
+        let vpc: Block = "10.0.0.0/16".parse().unwrap();
+        let subnet: Block = "10.0.4.0/22".parse().unwrap();
+
+        assert!(subnet.is_subnet_of(&vpc));
+        assert!(vpc.is_subnet_of(&vpc));
+        assert!(!vpc.is_subnet_of(&subnet));
+        assert!(!"10.1.0.0/24".parse::<Block>().unwrap().is_subnet_of(&vpc));
+
+        assert_eq!(subnet.supernet().unwrap().to_string(), "10.0.0.0/21");
+        assert_eq!(
+            "10.0.5.0/24"
+                .parse::<Block>()
+                .unwrap()
+                .supernet()
+                .unwrap()
+                .to_string(),
+            "10.0.4.0/23"
+        );
+        assert!("0.0.0.0/0".parse::<Block>().unwrap().supernet().is_none());
+
+        assert_eq!(vpc.size(), 65536);
+        assert_eq!(
+            "10.0.0.0/24".parse::<Block>().unwrap().aws_usable_hosts(),
+            251
+        );
+        assert_eq!(
+            "10.0.0.0/28".parse::<Block>().unwrap().aws_usable_hosts(),
+            11
+        );
+        assert_eq!(
+            "10.0.0.0/30".parse::<Block>().unwrap().aws_usable_hosts(),
+            0
+        );
+        assert_eq!("0.0.0.0/0".parse::<Block>().unwrap().size(), 1 << 32);
+    }
 }
@@ -0,0 +1,427 @@
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::Expression;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Most servers of each kind a DHCP options set accepts.
+const MAX_SERVERS: usize = 4;
+
+/// A DNS server handed out by DHCP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnsServer {
+    /// The Route 53 Resolver at the VPC's base address plus two, `AmazonProvidedDNS`.
+    AmazonProvided,
+    /// A server by address, such as an on-premises resolver.
+    Address(IpAddr),
+}
+
+impl fmt::Display for DnsServer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DnsServer::AmazonProvided => write!(f, "AmazonProvidedDNS"),
+            DnsServer::Address(address) => write!(f, "{}", address),
+        }
+    }
+}
+
+impl From<IpAddr> for DnsServer {
+    fn from(address: IpAddr) -> Self {
+        DnsServer::Address(address)
+    }
+}
+
+impl From<Ipv4Addr> for DnsServer {
+    fn from(address: Ipv4Addr) -> Self {
+        DnsServer::Address(address.into())
+    }
+}
+
+impl IntoExpression for DnsServer {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(Vec<DnsServer>);
+
+/// How NetBIOS clients resolve names. AWS does not support broadcast or multicast, so
+/// [`DhcpOptions::validate`] rejects `Broadcast` and `Mixed`, and point-to-point is the
+/// recommended value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetbiosNodeType {
+    Broadcast,
+    PointToPoint,
+    Mixed,
+    Hybrid,
+}
+
+impl fmt::Display for NetbiosNodeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetbiosNodeType::Broadcast => write!(f, "1"),
+            NetbiosNodeType::PointToPoint => write!(f, "2"),
+            NetbiosNodeType::Mixed => write!(f, "4"),
+            NetbiosNodeType::Hybrid => write!(f, "8"),
+        }
+    }
+}
+
+impl IntoExpression for NetbiosNodeType {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(NetbiosNodeType);
+
+/// Represents an AWS VPC DHCP Options Set resource. Attach it to a VPC with
+/// [`DhcpOptions::associate`].
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_dhcp_options")]
+pub struct DhcpOptions {
+    /// The name of the DHCP options set.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The DNS suffix instances use for unqualified names, such as `corp.example.com`.
+    pub domain_name: Option<Value<String>>,
+
+    /// Up to four DNS servers.
+    pub domain_name_servers: Option<Value<Vec<DnsServer>>>,
+
+    /// Up to four NTP servers, such as [`DhcpOptions::AMAZON_TIME_SYNC`].
+    pub ntp_servers: Option<Value<Vec<IpAddr>>>,
+
+    /// Up to four NetBIOS name servers.
+    pub netbios_name_servers: Option<Value<Vec<IpAddr>>>,
+
+    /// The NetBIOS node type.
+    pub netbios_node_type: Option<Value<NetbiosNodeType>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl DhcpOptions {
+    /// The Amazon Time Sync Service, reachable from every VPC.
+    pub const AMAZON_TIME_SYNC: Ipv4Addr = Ipv4Addr::new(169, 254, 169, 123);
+
+    /// Checks the DHCP options against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if self.domain_name.is_none()
+            && self.domain_name_servers.is_none()
+            && self.ntp_servers.is_none()
+            && self.netbios_name_servers.is_none()
+            && self.netbios_node_type.is_none()
+        {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "domain_name_servers",
+            });
+        }
+
+        let counts = [
+            (
+                "domain_name_servers",
+                self.domain_name_servers
+                    .as_ref()
+                    .and_then(Value::as_literal)
+                    .map(Vec::len),
+            ),
+            (
+                "ntp_servers",
+                self.ntp_servers
+                    .as_ref()
+                    .and_then(Value::as_literal)
+                    .map(Vec::len),
+            ),
+            (
+                "netbios_name_servers",
+                self.netbios_name_servers
+                    .as_ref()
+                    .and_then(Value::as_literal)
+                    .map(Vec::len),
+            ),
+        ];
+        for (argument, count) in counts {
+            match count {
+                Some(0) => {
+                    return Err(Error::invalid(
+                        Self::TYPE,
+                        argument,
+                        "must list at least one server",
+                    ))
+                }
+                Some(count) if count > MAX_SERVERS => {
+                    return Err(Error::invalid(
+                        Self::TYPE,
+                        argument,
+                        format!("at most {} servers are allowed, got {}", MAX_SERVERS, count),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(domain_name) = self.domain_name.as_ref().and_then(Value::as_literal) {
+            if domain_name.is_empty() || domain_name.contains(char::is_whitespace) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "domain_name",
+                    format!("`{}` is not a single domain name", domain_name),
+                ));
+            }
+        }
+
+        if let Some(node_type @ (NetbiosNodeType::Broadcast | NetbiosNodeType::Mixed)) =
+            self.netbios_node_type.as_ref().and_then(Value::as_literal)
+        {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "netbios_node_type",
+                format!(
+                    "node type {} relies on broadcast, which AWS does not support",
+                    node_type
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the options hand out the Route 53 Resolver, which only answers when the
+    /// VPC has DNS support enabled.
+    pub fn uses_amazon_dns(&self) -> bool {
+        self.domain_name_servers
+            .as_ref()
+            .and_then(Value::as_literal)
+            .is_some_and(|servers| servers.contains(&DnsServer::AmazonProvided))
+    }
+
+    /// Associates the options with `vpc`, replacing its current set.
+    pub fn associate<'a>(&'a self, name: &str, vpc: &'a Vpc) -> DhcpOptionsAssociation<'a> {
+        DhcpOptionsAssociation {
+            name: name.to_string(),
+            vpc,
+            dhcp_options: self,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    /// The ID of the DHCP options set.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the DHCP options set.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the AWS account that owns the DHCP options set.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+}
+
+/// Represents an AWS VPC DHCP Options Association resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_dhcp_options_association")]
+pub struct DhcpOptionsAssociation<'a> {
+    /// The name of the association.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC to use the options in.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// The DHCP options set.
+    #[hcl(reference = "id", rename = "dhcp_options_id")]
+    pub dhcp_options: &'a DhcpOptions,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl DhcpOptionsAssociation<'_> {
+    /// Checks the association against the constraints enforced by AWS, including that a
+    /// VPC without DNS support is not handed `AmazonProvidedDNS`.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        let dns_support = self
+            .vpc
+            .enable_dns_support
+            .as_ref()
+            .and_then(Value::as_literal);
+        if dns_support == Some(&false) && self.dhcp_options.uses_amazon_dns() {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "dhcp_options_id",
+                format!(
+                    "`{}` hands out AmazonProvidedDNS but `{}` disables `enable_dns_support`",
+                    self.dhcp_options.name, self.vpc.name
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the DHCP options set.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::Stack;
+    use hcl::Block;
+
+    fn vpc() -> Vpc {
+        Vpc {
+            enable_dns_hostnames: Some(true.into()),
+            enable_dns_support: Some(true.into()),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
+        }
+    }
+
+    fn hybrid() -> DhcpOptions {
+        DhcpOptions {
+            name: "hybrid".to_string(),
+            domain_name: Some("corp.example.com".into()),
+            domain_name_servers: Some(
+                vec![
+                    DnsServer::from(Ipv4Addr::new(192, 168, 10, 53)),
+                    DnsServer::AmazonProvided,
+                ]
+                .into(),
+            ),
+            ntp_servers: Some(vec![DhcpOptions::AMAZON_TIME_SYNC.into()].into()),
+            netbios_name_servers: None,
+            netbios_node_type: Some(NetbiosNodeType::PointToPoint.into()),
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    #[test]
+    fn test_dhcp_options_to_hcl() {
+        let vpc = vpc();
+        let options = hybrid();
+
+        let mut stack = Stack::new();
+        stack
+            .add(options.clone())
+            .unwrap()
+            .add(options.associate("main", &vpc))
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_vpc_dhcp_options" "hybrid""#));
+        assert!(hcl.contains(r#"domain_name = "corp.example.com""#));
+        assert!(hcl.contains(
+            r#"domain_name_servers = [
+    "192.168.10.53",
+    "AmazonProvidedDNS"
+  ]"#
+        ));
+        assert!(hcl.contains(r#""169.254.169.123""#));
+        assert!(hcl.contains(r#"netbios_node_type = "2""#));
+        assert!(hcl.contains(r#"resource "aws_vpc_dhcp_options_association" "main""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+        assert!(hcl.contains("dhcp_options_id = aws_vpc_dhcp_options.hybrid.id"));
+    }
+
+    #[test]
+    fn test_dhcp_options_rejects_invalid_settings() {
+        let too_many = DhcpOptions {
+            ntp_servers: Some(vec![IpAddr::from(DhcpOptions::AMAZON_TIME_SYNC); 5].into()),
+            ..hybrid()
+        };
+        assert!(matches!(
+            Block::try_from(too_many),
+            Err(Error::InvalidArgument {
+                argument: "ntp_servers",
+                ..
+            })
+        ));
+
+        for node_type in [NetbiosNodeType::Broadcast, NetbiosNodeType::Mixed] {
+            let broadcast = DhcpOptions {
+                netbios_node_type: Some(node_type.into()),
+                ..hybrid()
+            };
+            assert!(matches!(
+                Block::try_from(broadcast),
+                Err(Error::InvalidArgument {
+                    argument: "netbios_node_type",
+                    ..
+                })
+            ));
+        }
+
+        let spaced = DhcpOptions {
+            domain_name: Some("corp.example.com example.com".into()),
+            ..hybrid()
+        };
+        assert!(Block::try_from(spaced).is_err());
+
+        let empty = DhcpOptions {
+            domain_name: None,
+            domain_name_servers: None,
+            ntp_servers: None,
+            netbios_node_type: None,
+            ..hybrid()
+        };
+        assert!(matches!(
+            Block::try_from(empty),
+            Err(Error::MissingArgument { .. })
+        ));
+
+        let no_dns = Vpc {
+            enable_dns_hostnames: None,
+            enable_dns_support: Some(false.into()),
+            ..vpc()
+        };
+        let options = hybrid();
+        assert!(matches!(
+            Block::try_from(options.associate("main", &no_dns)),
+            Err(Error::InvalidArgument {
+                argument: "dhcp_options_id",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,476 @@
+use crate::aws::network::route::{RouteTable, RouteTarget};
+use crate::aws::network::security_group::SecurityGroup;
+use crate::aws::network::subnet::Subnet;
+pub use crate::aws::network::vpc::Filter;
+use crate::aws::network::vpc::Vpc;
+use crate::aws::region::{Partition, Region};
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::structure::BlockBuilder;
+use hcl::Expression;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Services that can be reached through gateway endpoints.
+const GATEWAY_SERVICES: [&str; 2] = ["s3", "dynamodb"];
+
+/// Services whose names in the China regions start with `cn.com.amazonaws` rather than
+/// `com.amazonaws`.
+const CN_PREFIXED_SERVICES: [&str; 2] = ["ecr.api", "ecr.dkr"];
+
+/// Name of an AWS service in a region, such as `com.amazonaws.us-east-1.s3`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServiceName {
+    region: Region,
+    service: String,
+    prefix: Option<String>,
+}
+
+impl ServiceName {
+    /// The endpoint service for `service` in `region`, where `service` is the endpoint
+    /// prefix such as `ecr.dkr` or `sts`.
+    pub fn new(region: Region, service: &str) -> Self {
+        ServiceName {
+            region,
+            service: service.to_string(),
+            prefix: None,
+        }
+    }
+
+    /// Overrides the prefix, for services whose names do not follow the partition's usual
+    /// one, such as `cn.com.amazonaws` for some services in the China regions.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Amazon S3, reachable through gateway and interface endpoints.
+    pub fn s3(region: Region) -> Self {
+        Self::new(region, "s3")
+    }
+
+    /// Amazon DynamoDB, reachable through gateway and interface endpoints.
+    pub fn dynamodb(region: Region) -> Self {
+        Self::new(region, "dynamodb")
+    }
+
+    /// The Amazon ECR API, used for authentication and image metadata.
+    pub fn ecr_api(region: Region) -> Self {
+        Self::new(region, "ecr.api")
+    }
+
+    /// The Docker registry API of Amazon ECR, used to pull images.
+    pub fn ecr_dkr(region: Region) -> Self {
+        Self::new(region, "ecr.dkr")
+    }
+
+    /// Region of the service.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// The service's endpoint prefix, such as `s3`.
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// The part of the name before the region: `com.amazonaws`, or `cn.com.amazonaws` for
+    /// the services that use it in the China regions, unless overridden.
+    pub fn prefix(&self) -> &str {
+        match &self.prefix {
+            Some(prefix) => prefix,
+            None if self.region.partition() == Partition::AwsCn
+                && CN_PREFIXED_SERVICES.contains(&self.service.as_str()) =>
+            {
+                "cn.com.amazonaws"
+            }
+            None => "com.amazonaws",
+        }
+    }
+
+    /// Whether the service can be reached through a gateway endpoint.
+    pub fn supports_gateway(&self) -> bool {
+        GATEWAY_SERVICES.contains(&self.service.as_str())
+    }
+}
+
+impl fmt::Display for ServiceName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.prefix(), self.region, self.service)
+    }
+}
+
+impl IntoExpression for ServiceName {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(ServiceName);
+
+/// How a VPC endpoint reaches its service.
+#[derive(Debug, Clone)]
+pub enum EndpointType<'a> {
+    /// Adds a route to the service's prefix list to each route table. Free, but only
+    /// available for S3 and DynamoDB.
+    Gateway {
+        /// Route tables that get a route to the service.
+        route_tables: Vec<&'a RouteTable<'a>>,
+    },
+    /// Places a network interface in each subnet (PrivateLink).
+    Interface {
+        /// Subnets to place a network interface in, at most one per availability zone.
+        subnets: Vec<&'a Subnet<'a>>,
+        /// Security groups of the network interfaces.
+        security_groups: Vec<&'a SecurityGroup<'a>>,
+        /// Resolves the service's default DNS name to the endpoint inside the VPC.
+        private_dns_enabled: Option<Value<bool>>,
+    },
+    /// Sends traffic to the appliances behind a Gateway Load Balancer endpoint service. The
+    /// only kind of endpoint that can be a route target.
+    GatewayLoadBalancer {
+        /// Subnet to place the endpoint's network interface in.
+        subnet: &'a Subnet<'a>,
+    },
+}
+
+impl EndpointType<'_> {
+    /// Adds `vpc_endpoint_type` and the type's attachments to an endpoint block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        fn ids<I: IntoIterator<Item = Reference>>(references: I) -> Expression {
+            Expression::Array(references.into_iter().map(Expression::from).collect())
+        }
+
+        match self {
+            EndpointType::Gateway { route_tables } => {
+                let block = block.add_attribute(("vpc_endpoint_type", "Gateway"));
+                if route_tables.is_empty() {
+                    return block;
+                }
+                block.add_attribute(("route_table_ids", ids(route_tables.iter().map(|t| t.id()))))
+            }
+            EndpointType::Interface {
+                subnets,
+                security_groups,
+                private_dns_enabled,
+            } => {
+                let mut block = block.add_attribute(("vpc_endpoint_type", "Interface"));
+                if !subnets.is_empty() {
+                    block =
+                        block.add_attribute(("subnet_ids", ids(subnets.iter().map(|s| s.id()))));
+                }
+                if !security_groups.is_empty() {
+                    block = block.add_attribute((
+                        "security_group_ids",
+                        ids(security_groups.iter().map(|g| g.id())),
+                    ));
+                }
+                if let Some(private_dns_enabled) = private_dns_enabled {
+                    block = block.add_attribute(("private_dns_enabled", private_dns_enabled));
+                }
+                block
+            }
+            EndpointType::GatewayLoadBalancer { subnet } => block
+                .add_attribute(("vpc_endpoint_type", "GatewayLoadBalancer"))
+                .add_attribute(("subnet_ids", ids([subnet.id()]))),
+        }
+    }
+}
+
+/// Represents an AWS VPC Endpoint resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_endpoint")]
+pub struct VpcEndpoint<'a> {
+    /// The name of the endpoint.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC the endpoint is created in.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// The service the endpoint connects to.
+    pub service_name: Value<ServiceName>,
+
+    /// Whether the endpoint is a gateway or interface endpoint, and what it is attached to.
+    #[hcl(flatten)]
+    pub endpoint_type: EndpointType<'a>,
+
+    /// A JSON IAM policy limiting what can be done through the endpoint.
+    pub policy: Option<Value<String>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl VpcEndpoint<'_> {
+    /// Checks the endpoint arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if let (Some(service_name), EndpointType::Gateway { .. }) =
+            (self.service_name.as_literal(), &self.endpoint_type)
+        {
+            if !service_name.supports_gateway() {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "vpc_endpoint_type",
+                    format!("`{}` does not support gateway endpoints", service_name),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the endpoint.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the endpoint.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The DNS entries of an interface endpoint.
+    pub fn dns_entry(&self) -> Reference {
+        self.attr("dns_entry")
+    }
+
+    /// The network interfaces of an interface endpoint.
+    pub fn network_interface_ids(&self) -> Reference {
+        self.attr("network_interface_ids")
+    }
+
+    /// The prefix list of the service of a gateway endpoint, for security group rules.
+    pub fn prefix_list_id(&self) -> Reference {
+        self.attr("prefix_list_id")
+    }
+}
+
+/// Only Gateway Load Balancer endpoints can be route targets; other endpoints are rejected.
+impl TryFrom<&VpcEndpoint<'_>> for RouteTarget {
+    type Error = Error;
+
+    fn try_from(endpoint: &VpcEndpoint<'_>) -> Result<Self, Self::Error> {
+        match endpoint.endpoint_type {
+            EndpointType::GatewayLoadBalancer { .. } => {
+                Ok(RouteTarget::VpcEndpoint(endpoint.id().into()))
+            }
+            EndpointType::Gateway { .. } | EndpointType::Interface { .. } => Err(Error::invalid(
+                "aws_route",
+                "vpc_endpoint_id",
+                format!(
+                    "`{}` is not a Gateway Load Balancer endpoint, so it cannot be a route target",
+                    endpoint.name
+                ),
+            )),
+        }
+    }
+}
+
+/// Represents a data source for an AWS VPC Endpoint.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_vpc_endpoint")]
+pub struct VpcEndpointDataSource {
+    /// The name of the endpoint data source.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The ID of the specific endpoint to retrieve.
+    pub id: Option<String>,
+
+    /// The service name of the endpoint, such as `com.amazonaws.us-east-1.s3`.
+    pub service_name: Option<String>,
+
+    /// The state of the endpoint.
+    pub state: Option<String>,
+
+    /// The ID of the VPC the endpoint is in.
+    pub vpc_id: Option<String>,
+
+    /// One or more name-value pairs to filter by.
+    #[hcl(block)]
+    pub filter: Option<Vec<Filter>>,
+
+    /// A map of tags the endpoint must have.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+impl VpcEndpointDataSource {
+    /// The ID of the endpoint.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The prefix list of the service of a gateway endpoint.
+    pub fn prefix_list_id(&self) -> Reference {
+        self.attr("prefix_list_id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hcl::Block;
+
+    #[test]
+    fn test_service_names() {
+        assert_eq!(
+            ServiceName::s3(Region::UsEast1).to_string(),
+            "com.amazonaws.us-east-1.s3"
+        );
+        assert_eq!(
+            ServiceName::ecr_dkr(Region::EuWest1).to_string(),
+            "com.amazonaws.eu-west-1.ecr.dkr"
+        );
+        assert_eq!(
+            ServiceName::ecr_dkr(Region::CnNorth1).to_string(),
+            "cn.com.amazonaws.cn-north-1.ecr.dkr"
+        );
+        assert_eq!(
+            ServiceName::s3(Region::CnNorthwest1).to_string(),
+            "com.amazonaws.cn-northwest-1.s3"
+        );
+        assert_eq!(
+            ServiceName::new(Region::CnNorth1, "sagemaker.api")
+                .with_prefix("cn.com.amazonaws")
+                .to_string(),
+            "cn.com.amazonaws.cn-north-1.sagemaker.api"
+        );
+        assert!(ServiceName::dynamodb(Region::UsEast1).supports_gateway());
+        assert!(!ServiceName::ecr_api(Region::UsEast1).supports_gateway());
+    }
+
+    #[test]
+    fn test_gateway_endpoint_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let private = RouteTable {
+            name: "private".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let endpoint = VpcEndpoint {
+            name: "s3".to_string(),
+            vpc: &vpc,
+            service_name: ServiceName::s3(Region::UsEast1).into(),
+            endpoint_type: EndpointType::Gateway {
+                route_tables: vec![&private],
+            },
+            policy: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        assert!(matches!(
+            RouteTarget::try_from(&endpoint),
+            Err(Error::InvalidArgument {
+                argument: "vpc_endpoint_id",
+                ..
+            })
+        ));
+
+        let hcl = hcl::to_string(&Block::try_from(endpoint.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_vpc_endpoint" "s3""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+        assert!(hcl.contains(r#"service_name = "com.amazonaws.us-east-1.s3""#));
+        assert!(hcl.contains(r#"vpc_endpoint_type = "Gateway""#));
+        assert!(hcl.contains(
+            r#"route_table_ids = [
+    aws_route_table.private.id
+  ]"#
+        ));
+
+        let ecr = VpcEndpoint {
+            service_name: ServiceName::ecr_api(Region::UsEast1).into(),
+            ..endpoint
+        };
+        assert!(matches!(
+            Block::try_from(ecr),
+            Err(Error::InvalidArgument {
+                argument: "vpc_endpoint_type",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_interface_endpoint_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let subnet = Subnet::new("private", &vpc, "10.0.10.0/24".parse().unwrap());
+        let endpoints = SecurityGroup {
+            name: "endpoints".to_string(),
+            description: None,
+            vpc: &vpc,
+            revoke_rules_on_delete: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let endpoint = VpcEndpoint {
+            name: "ecr_dkr".to_string(),
+            vpc: &vpc,
+            service_name: ServiceName::ecr_dkr(Region::UsEast1).into(),
+            endpoint_type: EndpointType::Interface {
+                subnets: vec![&subnet],
+                security_groups: vec![&endpoints],
+                private_dns_enabled: Some(true.into()),
+            },
+            policy: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        assert!(RouteTarget::try_from(&endpoint).is_err());
+
+        let hcl = hcl::to_string(&Block::try_from(endpoint).unwrap()).unwrap();
+        assert!(hcl.contains(r#"vpc_endpoint_type = "Interface""#));
+        assert!(hcl.contains("aws_subnet.private.id"));
+        assert!(hcl.contains("aws_security_group.endpoints.id"));
+        assert!(hcl.contains("private_dns_enabled = true"));
+
+        let inspection = VpcEndpoint {
+            name: "inspection".to_string(),
+            vpc: &vpc,
+            service_name: Reference::resource("aws_vpc_endpoint_service", "inspection")
+                .attr("service_name")
+                .into(),
+            endpoint_type: EndpointType::GatewayLoadBalancer { subnet: &subnet },
+            policy: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        assert!(matches!(
+            RouteTarget::try_from(&inspection),
+            Ok(RouteTarget::VpcEndpoint(_))
+        ));
+
+        let hcl = hcl::to_string(&Block::try_from(inspection).unwrap()).unwrap();
+        assert!(hcl.contains(r#"vpc_endpoint_type = "GatewayLoadBalancer""#));
+        assert!(hcl.contains("service_name = aws_vpc_endpoint_service.inspection.service_name"));
+        assert!(hcl.contains(
+            r#"subnet_ids = [
+    aws_subnet.private.id
+  ]"#
+        ));
+    }
+}
@@ -0,0 +1,579 @@
+use crate::aws::cloudwatch::LogGroup;
+use crate::aws::iam::{Role, RolePolicy};
+use crate::aws::network::subnet::Subnet;
+use crate::aws::network::vpc::Vpc;
+use crate::aws::region::Partition;
+use crate::aws::storage::s3::Bucket;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::stack::Stack;
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::expr::{FuncCall, TemplateExpr};
+use hcl::structure::BlockBuilder;
+use hcl::Expression;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Actions a delivery role needs on its log group.
+const CLOUDWATCH_ACTIONS: [&str; 4] = [
+    "logs:CreateLogStream",
+    "logs:PutLogEvents",
+    "logs:DescribeLogGroups",
+    "logs:DescribeLogStreams",
+];
+
+/// Which traffic is logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficType {
+    Accept,
+    Reject,
+    All,
+}
+
+impl fmt::Display for TrafficType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrafficType::Accept => write!(f, "ACCEPT"),
+            TrafficType::Reject => write!(f, "REJECT"),
+            TrafficType::All => write!(f, "ALL"),
+        }
+    }
+}
+
+impl IntoExpression for TrafficType {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(TrafficType);
+
+/// A field of a flow log record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogField {
+    Version,
+    AccountId,
+    InterfaceId,
+    SrcAddr,
+    DstAddr,
+    SrcPort,
+    DstPort,
+    Protocol,
+    Packets,
+    Bytes,
+    Start,
+    End,
+    Action,
+    LogStatus,
+    VpcId,
+    SubnetId,
+    InstanceId,
+    TcpFlags,
+    Type,
+    PktSrcAddr,
+    PktDstAddr,
+    Region,
+    AzId,
+    SublocationType,
+    SublocationId,
+    PktSrcAwsService,
+    PktDstAwsService,
+    FlowDirection,
+    TrafficPath,
+}
+
+impl LogField {
+    /// Fields of the default format, in order.
+    pub const DEFAULT: [LogField; 14] = [
+        LogField::Version,
+        LogField::AccountId,
+        LogField::InterfaceId,
+        LogField::SrcAddr,
+        LogField::DstAddr,
+        LogField::SrcPort,
+        LogField::DstPort,
+        LogField::Protocol,
+        LogField::Packets,
+        LogField::Bytes,
+        LogField::Start,
+        LogField::End,
+        LogField::Action,
+        LogField::LogStatus,
+    ];
+}
+
+impl fmt::Display for LogField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            LogField::Version => "version",
+            LogField::AccountId => "account-id",
+            LogField::InterfaceId => "interface-id",
+            LogField::SrcAddr => "srcaddr",
+            LogField::DstAddr => "dstaddr",
+            LogField::SrcPort => "srcport",
+            LogField::DstPort => "dstport",
+            LogField::Protocol => "protocol",
+            LogField::Packets => "packets",
+            LogField::Bytes => "bytes",
+            LogField::Start => "start",
+            LogField::End => "end",
+            LogField::Action => "action",
+            LogField::LogStatus => "log-status",
+            LogField::VpcId => "vpc-id",
+            LogField::SubnetId => "subnet-id",
+            LogField::InstanceId => "instance-id",
+            LogField::TcpFlags => "tcp-flags",
+            LogField::Type => "type",
+            LogField::PktSrcAddr => "pkt-srcaddr",
+            LogField::PktDstAddr => "pkt-dstaddr",
+            LogField::Region => "region",
+            LogField::AzId => "az-id",
+            LogField::SublocationType => "sublocation-type",
+            LogField::SublocationId => "sublocation-id",
+            LogField::PktSrcAwsService => "pkt-src-aws-service",
+            LogField::PktDstAwsService => "pkt-dst-aws-service",
+            LogField::FlowDirection => "flow-direction",
+            LogField::TrafficPath => "traffic-path",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The fields of each flow log record, in order, rendered as `${version} ${vpc-id} ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFormat(pub Vec<LogField>);
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields: Vec<String> = self
+            .0
+            .iter()
+            .map(|field| format!("${{{}}}", field))
+            .collect();
+        f.write_str(&fields.join(" "))
+    }
+}
+
+impl IntoExpression for LogFormat {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(LogFormat);
+
+/// What a flow log captures traffic for.
+#[derive(Debug, Clone)]
+pub enum FlowLogSource<'a> {
+    Vpc(&'a Vpc),
+    Subnet(&'a Subnet<'a>),
+    /// An elastic network interface, by ID.
+    NetworkInterface(Value<String>),
+}
+
+impl FlowLogSource<'_> {
+    /// Adds the `vpc_id`, `subnet_id` or `eni_id` argument to a flow log block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            FlowLogSource::Vpc(vpc) => block.add_attribute(("vpc_id", vpc.id())),
+            FlowLogSource::Subnet(subnet) => block.add_attribute(("subnet_id", subnet.id())),
+            FlowLogSource::NetworkInterface(id) => block.add_attribute(("eni_id", id)),
+        }
+    }
+}
+
+impl<'a> From<&'a Vpc> for FlowLogSource<'a> {
+    fn from(vpc: &'a Vpc) -> Self {
+        FlowLogSource::Vpc(vpc)
+    }
+}
+
+impl<'a> From<&'a Subnet<'a>> for FlowLogSource<'a> {
+    fn from(subnet: &'a Subnet<'a>) -> Self {
+        FlowLogSource::Subnet(subnet)
+    }
+}
+
+/// Where flow log records are delivered.
+#[derive(Debug, Clone)]
+pub enum FlowLogDestination<'a> {
+    /// An S3 bucket, optionally under a key prefix.
+    S3 {
+        bucket: &'a Bucket,
+        prefix: Option<String>,
+    },
+    /// A CloudWatch Logs log group, written to by a role the flow logs service can assume.
+    CloudWatchLogs {
+        log_group: &'a LogGroup,
+        iam_role_arn: Value<String>,
+    },
+}
+
+impl FlowLogDestination<'_> {
+    /// Adds `log_destination_type`, `log_destination` and, for CloudWatch Logs,
+    /// `iam_role_arn` to a flow log block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            FlowLogDestination::S3 { bucket, prefix } => {
+                let destination = match prefix {
+                    Some(prefix) => Expression::TemplateExpr(Box::new(TemplateExpr::QuotedString(
+                        format!("${{{}}}/{}", bucket.arn(), prefix.trim_matches('/')),
+                    ))),
+                    None => bucket.arn().into(),
+                };
+                block
+                    .add_attribute(("log_destination_type", "s3"))
+                    .add_attribute(("log_destination", destination))
+            }
+            FlowLogDestination::CloudWatchLogs {
+                log_group,
+                iam_role_arn,
+            } => block
+                .add_attribute(("log_destination_type", "cloud-watch-logs"))
+                .add_attribute(("log_destination", log_group.arn()))
+                .add_attribute(("iam_role_arn", iam_role_arn)),
+        }
+    }
+}
+
+/// Represents an AWS VPC Flow Log resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_flow_log")]
+pub struct FlowLog<'a> {
+    /// The name of the flow log.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC, subnet or network interface to capture traffic for.
+    #[hcl(flatten)]
+    pub source: FlowLogSource<'a>,
+
+    /// Which traffic to capture.
+    pub traffic_type: Value<TrafficType>,
+
+    /// Where records are delivered.
+    #[hcl(flatten)]
+    pub destination: FlowLogDestination<'a>,
+
+    /// The fields of each record. Defaults to [`LogField::DEFAULT`].
+    pub log_format: Option<Value<LogFormat>>,
+
+    /// Seconds over which packets are aggregated into a record, 60 or 600.
+    pub max_aggregation_interval: Option<Value<u32>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl<'a> FlowLog<'a> {
+    /// Logs all traffic of `source` to `bucket`.
+    pub fn to_s3(name: &str, source: impl Into<FlowLogSource<'a>>, bucket: &'a Bucket) -> Self {
+        Self::all_traffic(
+            name,
+            source.into(),
+            FlowLogDestination::S3 {
+                bucket,
+                prefix: None,
+            },
+        )
+    }
+
+    /// Logs all traffic of `source` to `log_group`, delivered by the role with ARN
+    /// `iam_role_arn`, such as one created with [`cloudwatch_role`].
+    pub fn to_cloudwatch(
+        name: &str,
+        source: impl Into<FlowLogSource<'a>>,
+        log_group: &'a LogGroup,
+        iam_role_arn: impl Into<Value<String>>,
+    ) -> Self {
+        Self::all_traffic(
+            name,
+            source.into(),
+            FlowLogDestination::CloudWatchLogs {
+                log_group,
+                iam_role_arn: iam_role_arn.into(),
+            },
+        )
+    }
+
+    /// Logs all traffic of `source` to `log_group`, delivered by a role named
+    /// `<name>-flow-logs` that is created along with its policy. The role trusts the flow
+    /// logs service of `partition`.
+    pub fn to_cloudwatch_with_role(
+        name: &str,
+        source: impl Into<FlowLogSource<'a>>,
+        log_group: &'a LogGroup,
+        partition: Partition,
+    ) -> CloudWatchFlowLog<'a> {
+        let role = cloudwatch_role(&format!("{}-flow-logs", name), partition);
+        CloudWatchFlowLog {
+            flow_log: Self::to_cloudwatch(name, source, log_group, role.arn()),
+            role,
+            log_group,
+        }
+    }
+
+    fn all_traffic(
+        name: &str,
+        source: FlowLogSource<'a>,
+        destination: FlowLogDestination<'a>,
+    ) -> Self {
+        FlowLog {
+            name: name.to_string(),
+            source,
+            traffic_type: TrafficType::All.into(),
+            destination,
+            log_format: None,
+            max_aggregation_interval: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    /// Checks the flow log arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if let Some(format) = self.log_format.as_ref().and_then(Value::as_literal) {
+            if format.0.is_empty() {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "log_format",
+                    "must include at least one field",
+                ));
+            }
+        }
+
+        if let Some(interval) = self
+            .max_aggregation_interval
+            .as_ref()
+            .and_then(Value::as_literal)
+        {
+            if *interval != 60 && *interval != 600 {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "max_aggregation_interval",
+                    format!("must be 60 or 600 seconds, got {}", interval),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the flow log.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the flow log.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+/// A role the flow logs service of `partition` can assume to deliver records to CloudWatch
+/// Logs. Grant it access to the log group with [`cloudwatch_role_policy`].
+pub fn cloudwatch_role(name: &str, partition: Partition) -> Role {
+    let principal = BTreeMap::from([(
+        "Service".to_string(),
+        Expression::from(partition.service_principal("vpc-flow-logs")),
+    )]);
+    let statement = BTreeMap::from([
+        ("Effect".to_string(), Expression::from("Allow")),
+        ("Principal".to_string(), principal.into_expression()),
+        ("Action".to_string(), Expression::from("sts:AssumeRole")),
+    ]);
+
+    Role {
+        name: name.to_string(),
+        assume_role_policy: policy_document(statement).into(),
+        description: Some("Delivers VPC flow logs to CloudWatch Logs".into()),
+        path: None,
+        max_session_duration: None,
+        permissions_boundary: None,
+        tags: None,
+        meta: MetaArguments::default(),
+    }
+}
+
+/// An inline policy letting `role` write flow log records to `log_group`, named after the
+/// role.
+pub fn cloudwatch_role_policy<'a>(role: &'a Role, log_group: &LogGroup) -> RolePolicy<'a> {
+    let arn = log_group.arn();
+    let log_streams = Expression::TemplateExpr(Box::new(TemplateExpr::QuotedString(format!(
+        "${{{}}}:*",
+        arn
+    ))));
+    let statement = BTreeMap::from([
+        ("Effect".to_string(), Expression::from("Allow")),
+        (
+            "Action".to_string(),
+            Expression::Array(CLOUDWATCH_ACTIONS.map(Expression::from).to_vec()),
+        ),
+        (
+            "Resource".to_string(),
+            Expression::Array(vec![arn.into(), log_streams]),
+        ),
+    ]);
+
+    RolePolicy {
+        name: role.name.clone(),
+        role,
+        policy: policy_document(statement).into(),
+        meta: MetaArguments::default(),
+    }
+}
+
+/// A `jsonencode(...)` IAM policy document with a single statement.
+fn policy_document(statement: BTreeMap<String, Expression>) -> Expression {
+    let document = BTreeMap::from([
+        ("Version".to_string(), Expression::from("2012-10-17")),
+        (
+            "Statement".to_string(),
+            Expression::Array(vec![statement.into_expression()]),
+        ),
+    ]);
+
+    FuncCall::builder("jsonencode")
+        .arg(document.into_expression())
+        .build()
+        .into()
+}
+
+/// A flow log to CloudWatch Logs together with the role that delivers its records, from
+/// [`FlowLog::to_cloudwatch_with_role`].
+#[derive(Debug, Clone)]
+pub struct CloudWatchFlowLog<'a> {
+    pub flow_log: FlowLog<'a>,
+    pub role: Role,
+    log_group: &'a LogGroup,
+}
+
+impl CloudWatchFlowLog<'_> {
+    /// The inline policy letting the role write to the log group.
+    pub fn role_policy(&self) -> RolePolicy<'_> {
+        cloudwatch_role_policy(&self.role, self.log_group)
+    }
+
+    /// Adds the role, its policy and the flow log to `stack`.
+    pub fn add_to<'s>(&self, stack: &'s mut Stack) -> Result<&'s mut Stack, Error> {
+        stack
+            .add(self.role.clone())?
+            .add(self.role_policy())?
+            .add(self.flow_log.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::region::Region;
+    use crate::aws::storage::s3::ACLOptions;
+
+    #[test]
+    fn test_flow_log_to_s3() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let bucket = Bucket {
+            name: Some("flow-logs".to_string()),
+            prefix: None,
+            force_destroy: None,
+            object_lock_enabled: None,
+            acl: Some(ACLOptions::Private.into()),
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let flow_log = FlowLog::to_s3("main", &vpc, &bucket);
+        let hcl = hcl::to_string(&hcl::Block::try_from(flow_log.clone()).unwrap()).unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_flow_log" "main""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+        assert!(hcl.contains(r#"traffic_type = "ALL""#));
+        assert!(hcl.contains(r#"log_destination_type = "s3""#));
+        assert!(hcl.contains("log_destination = aws_s3_bucket.flow-logs.arn"));
+
+        let formatted = FlowLog {
+            destination: FlowLogDestination::S3 {
+                bucket: &bucket,
+                prefix: Some("vpc/".to_string()),
+            },
+            log_format: Some(
+                LogFormat(vec![LogField::VpcId, LogField::SrcAddr, LogField::Action]).into(),
+            ),
+            ..flow_log.clone()
+        };
+        let hcl = hcl::to_string(&hcl::Block::try_from(formatted).unwrap()).unwrap();
+        assert!(hcl.contains(r#"log_destination = "${aws_s3_bucket.flow-logs.arn}/vpc""#));
+        assert!(hcl.contains(r#"log_format = "$${vpc-id} $${srcaddr} $${action}""#));
+
+        let slow = FlowLog {
+            max_aggregation_interval: Some(300.into()),
+            ..flow_log
+        };
+        assert!(hcl::Block::try_from(slow).is_err());
+    }
+
+    #[test]
+    fn test_flow_log_to_cloudwatch() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let log_group = LogGroup {
+            name: "flow_logs".to_string(),
+            log_group_name: "/aws/vpc/main".into(),
+            retention_in_days: Some(30.into()),
+            kms_key_id: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let role = cloudwatch_role("flow-logs", Partition::Aws);
+        let policy = cloudwatch_role_policy(&role, &log_group);
+
+        let mut stack = Stack::new();
+        stack
+            .add(FlowLog::to_cloudwatch("main", &vpc, &log_group, role.arn()))
+            .unwrap()
+            .add(policy)
+            .unwrap()
+            .add(role.clone())
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"log_destination_type = "cloud-watch-logs""#));
+        assert!(hcl.contains("log_destination = aws_cloudwatch_log_group.flow_logs.arn"));
+        assert!(hcl.contains("iam_role_arn = aws_iam_role.flow-logs.arn"));
+        assert!(hcl.contains(r#"Service = "vpc-flow-logs.amazonaws.com""#));
+        assert!(hcl.contains(r#"Action = "sts:AssumeRole""#));
+        assert!(hcl.contains(r#"resource "aws_iam_role_policy" "flow-logs""#));
+        assert!(hcl.contains("jsonencode("));
+        assert!(hcl.contains(r#"Version = "2012-10-17""#));
+        assert!(hcl.contains(r#""logs:PutLogEvents""#));
+        assert!(hcl.contains("aws_cloudwatch_log_group.flow_logs.arn,"));
+        assert!(hcl.contains(r#""${aws_cloudwatch_log_group.flow_logs.arn}:*""#));
+
+        let mut stack = Stack::new();
+        FlowLog::to_cloudwatch_with_role("main", &vpc, &log_group, Region::CnNorth1.partition())
+            .add_to(&mut stack)
+            .unwrap();
+        let hcl = stack.render().unwrap();
+        assert!(hcl.contains(r#"resource "aws_iam_role" "main-flow-logs""#));
+        assert!(hcl.contains(r#"resource "aws_iam_role_policy" "main-flow-logs""#));
+        assert!(hcl.contains("role = aws_iam_role.main-flow-logs.id"));
+        assert!(hcl.contains(r#"resource "aws_flow_log" "main""#));
+        assert!(hcl.contains("iam_role_arn = aws_iam_role.main-flow-logs.arn"));
+        assert!(hcl.contains(r#"Service = "vpc-flow-logs.amazonaws.com.cn""#));
+    }
+}
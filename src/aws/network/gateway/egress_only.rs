@@ -0,0 +1,126 @@
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use std::collections::HashMap;
+
+/// Represents an AWS Egress-Only Internet Gateway resource, which lets IPv6 traffic out of
+/// a VPC while blocking connections initiated from the internet.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_egress_only_internet_gateway")]
+pub struct EgressOnlyInternet<'a> {
+    /// The name of the Egress-Only Internet Gateway.
+    #[hcl(label)]
+    pub name: String,
+    /// The VPC to which the gateway is attached.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl EgressOnlyInternet<'_> {
+    /// Checks the gateway arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the Egress-Only Internet Gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::cidr;
+    use crate::aws::network::route::{Route, RouteDestination, RouteTable};
+    use crate::aws::network::subnet::Subnet;
+    use crate::stack::Stack;
+    use hcl::Block;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_dual_stack_egress_to_hcl() {
+        let vpc = Vpc {
+            assign_generated_ipv6_cidr_block: Some(true.into()),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
+        };
+        let subnet = Subnet {
+            assign_ipv6_address_on_creation: Some(true.into()),
+            ipv6_cidr_block: Some(vpc.ipv6_subnet(1).into()),
+            ..Subnet::new("private_a", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+        let gateway = EgressOnlyInternet {
+            name: "main".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let route_table = RouteTable {
+            name: "private".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let route = Route {
+            name: "private_ipv6".to_string(),
+            route_table: &route_table,
+            destination: RouteDestination::Ipv6(
+                cidr::Ipv6Block::new(Ipv6Addr::UNSPECIFIED, 0)
+                    .unwrap()
+                    .into(),
+            ),
+            target: (&gateway).into(),
+            meta: MetaArguments::default(),
+        };
+
+        let mut stack = Stack::new();
+        stack
+            .add(subnet)
+            .unwrap()
+            .add(gateway.clone())
+            .unwrap()
+            .add(route.clone())
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains("ipv6_cidr_block = cidrsubnet(aws_vpc.main.ipv6_cidr_block, 8, 1)"));
+        assert!(hcl.contains(r#"resource "aws_egress_only_internet_gateway" "main""#));
+        assert!(hcl.contains(r#"destination_ipv6_cidr_block = "::/0""#));
+        assert!(hcl.contains("egress_only_gateway_id = aws_egress_only_internet_gateway.main.id"));
+
+        let ipv4_route = Route {
+            destination: RouteDestination::Ipv4(
+                cidr::Block::new(Ipv4Addr::new(0, 0, 0, 0), 0)
+                    .unwrap()
+                    .into(),
+            ),
+            ..route
+        };
+        assert!(matches!(
+            Block::try_from(ipv4_route),
+            Err(Error::InvalidArgument {
+                argument: "egress_only_gateway_id",
+                ..
+            })
+        ));
+    }
+}
@@ -1,149 +1,127 @@
+pub use crate::aws::network::vpc::Filter;
 use crate::aws::network::vpc::Vpc;
-use hcl::{Block, Expression, ObjectKey};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
 use std::collections::HashMap;
 
 /// Represents an AWS Internet Gateway resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_internet_gateway")]
 pub struct Internet<'a> {
     /// The name of the Internet Gateway.
+    #[hcl(label)]
     pub name: String,
     /// The VPC to which the Internet Gateway is attached.
+    #[hcl(reference = "id")]
     pub vpc: &'a Vpc,
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
 }
 
-impl<'a> From<Internet<'a>> for Block {
-    fn from(internet: Internet<'a>) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_internet_gateway")
-            .add_label(&internet.name)
-            .add_attribute((
-                "vpc_id",
-                Expression::from(format!("${{aws_vpc.{}.id}}", internet.vpc.name)),
-            ));
-
-        match internet.tags {
-            Some(tags) => {
-                let tags_expr = Expression::Object(
-                    tags.into_iter()
-                        .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                        .collect(),
-                );
-                block = block.add_attribute(("tags", tags_expr));
-            }
-            None => (),
+impl Internet<'_> {
+    /// Checks the Internet Gateway arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
         }
 
-        block.build()
+        Ok(())
+    }
+
+    /// The ID of the Internet Gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the Internet Gateway.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the AWS account that owns the internet gateway.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
     }
 }
 
 /// Represents a data source for an AWS Internet Gateway.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_internet_gateway")]
 pub struct InternetDataSource {
     /// The name of the Internet Gateway data source.
+    #[hcl(label)]
     pub name: String,
 
     /// The ID of the specific Internet Gateway to retrieve.
     pub internet_gateway_id: Option<String>,
 
     /// One or more name-value pairs to filter by.
+    #[hcl(block)]
     pub filter: Option<Vec<Filter>>,
 
     /// A map of tags to assign to the resource.
     pub tags: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Filter {
-    pub name: String,
-    pub values: Vec<String>,
-}
-
-impl From<InternetDataSource> for Block {
-    fn from(data_source: InternetDataSource) -> Self {
-        let mut block = Block::builder("data")
-            .add_label("aws_internet_gateway")
-            .add_label(&data_source.name);
-
-        if let Some(id) = data_source.internet_gateway_id {
-            block = block.add_attribute(("internet_gateway_id", Expression::String(id)));
-        }
-
-        if let Some(tags) = data_source.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
-
-        if let Some(filters) = data_source.filter {
-            let filter_blocks: Vec<Block> = filters
-                .into_iter()
-                .map(|f| {
-                    Block::builder("filter")
-                        .add_attribute(("name", Expression::String(f.name)))
-                        .add_attribute((
-                            "values",
-                            Expression::Array(
-                                f.values.into_iter().map(Expression::String).collect(),
-                            ),
-                        ))
-                        .build()
-                })
-                .collect();
-            block = block.add_blocks(filter_blocks);
-        }
+impl InternetDataSource {
+    /// The ID of the Internet Gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
 
-        block.build()
+    /// The ARN of the Internet Gateway.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hcl::Block;
 
     #[test]
     fn test_internet_gateway_to_hcl() {
-        let vpc = Vpc {
-            name: "test-vpc".to_string(),
-            cidr_block: "10.0.0.0/16".to_string(),
-            instance_tenancy: None,
-            enable_dns_hostnames: None,
-            enable_dns_support: None,
-            enable_classiclink: None,
-            enable_classiclink_dns_support: None,
-            assign_generated_ipv6_cidr_block: None,
-            tags: None,
-        };
+        let vpc = Vpc::new("test-vpc", "10.0.0.0/16".parse().unwrap());
 
-        let internet_gateway = Gateway {
+        let internet_gateway = Internet {
             name: "main-igw".to_string(),
             vpc: &vpc,
-            tags: Some(HashMap::from([
-                ("Name".to_string(), "Main Internet Gateway".to_string()),
-                ("Environment".to_string(), "Production".to_string()),
-            ])),
-            vpc_ipv6_cidr_block: Some(true),
+            tags: Some(
+                HashMap::from([
+                    ("Name".to_string(), "Main Internet Gateway".to_string()),
+                    ("Environment".to_string(), "Production".to_string()),
+                ])
+                .into(),
+            ),
+            meta: MetaArguments::default(),
         };
 
-        let block: Block = internet_gateway.into();
+        let block = Block::try_from(internet_gateway).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
 
         assert!(hcl.contains(r#"resource "aws_internet_gateway" "main-igw""#));
-        assert!(hcl.contains(r#"vpc_id = ${aws_vpc.test-vpc.id}"#));
+        assert!(hcl.contains(r#"vpc_id = aws_vpc.test-vpc.id"#));
         assert!(hcl.contains(r#"tags = {"#));
         assert!(hcl.contains(r#""Name" = "Main Internet Gateway""#));
         assert!(hcl.contains(r#""Environment" = "Production""#));
-        assert!(hcl.contains(r#"vpc_ipv6_cidr_block = true"#));
     }
 
     #[test]
     fn test_internet_gateway_data_source_to_hcl() {
-        let data_source = InternetGatewayDataSource {
+        let data_source = InternetDataSource {
             name: "main-igw".to_string(),
             internet_gateway_id: Some("igw-12345".to_string()),
             tags: Some(HashMap::from([(
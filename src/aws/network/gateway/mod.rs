@@ -1,5 +1,7 @@
+pub mod egress_only;
 pub mod internet;
 pub mod nat;
 
+pub use egress_only::EgressOnlyInternet;
 pub use internet::Internet;
 pub use nat::NAT;
@@ -1,81 +1,118 @@
 use crate::aws::network::subnet::Subnet;
 use crate::aws::network::vpc::ElasticIp;
+pub use crate::aws::network::vpc::Filter;
 use crate::aws::network::vpc::Vpc;
-use hcl::{Block, Expression, ObjectKey};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
 use std::collections::HashMap;
 
 /// Represents an AWS NAT Gateway resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_nat_gateway")]
 pub struct NAT<'a> {
     /// The ID of the NAT Gateway.
+    #[hcl(skip)]
     pub id: Option<String>,
 
     /// The VPC in which the NAT Gateway is located.
+    #[hcl(skip)]
     pub vpc: &'a Vpc,
 
     /// The Subnet in which the NAT Gateway is located.
+    #[hcl(reference = "id")]
     pub subnet: &'a Subnet<'a>,
 
-    /// The Elastic IP associated with the NAT Gateway.
-    pub elastic_ip: &'a ElasticIp,
+    /// The Elastic IP associated with the NAT Gateway. Required for public NAT Gateways and
+    /// not allowed for private ones.
+    #[hcl(reference = "allocation_id", rename = "allocation_id")]
+    pub elastic_ip: Option<&'a ElasticIp>,
 
     /// The connectivity type for the NAT Gateway. Valid values are private and public.
-    pub connectivity_type: Option<String>,
+    pub connectivity_type: Option<Value<String>>,
 
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
 
     /// A state of the NAT Gateway (pending | failed | available | deleting | deleted).
-    pub state: Option<String>,
+    pub state: Option<Value<String>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
 }
 
-impl<'a> From<NAT<'a>> for Block {
-    fn from(nat: NAT<'a>) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_nat_gateway")
-            .add_label(nat.id.as_deref().unwrap_or("nat"))
-            .add_attribute((
-                "subnet_id",
-                Expression::from(format!("${{aws_subnet.{}.id}}", nat.subnet.name)),
-            ))
-            .add_attribute((
-                "allocation_id",
-                Expression::from(format!("${{aws_eip.{}.id}}", nat.elastic_ip.name)),
-            ));
-
-        match nat.connectivity_type {
-            Some(connectivity_type) => {
-                block = block
-                    .add_attribute(("connectivity_type", Expression::String(connectivity_type)))
-            }
-            None => (),
-        }
+impl Resource for NAT<'_> {
+    const TYPE: &'static str = "aws_nat_gateway";
 
-        match nat.state {
-            Some(state) => block = block.add_attribute(("state", Expression::String(state))),
-            None => (),
-        }
+    fn name(&self) -> &str {
+        self.id.as_deref().unwrap_or("nat")
+    }
+}
 
-        match nat.tags {
-            Some(tags) => {
-                let tags_expr = Expression::Object(
-                    tags.into_iter()
-                        .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                        .collect(),
-                );
-                block = block.add_attribute(("tags", tags_expr));
-            }
-            None => (),
+impl NAT<'_> {
+    /// Checks the NAT Gateway arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        match self
+            .connectivity_type
+            .as_ref()
+            .and_then(Value::as_literal)
+            .map(String::as_str)
+        {
+            None | Some("public") if self.elastic_ip.is_none() => Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "allocation_id",
+            }),
+            Some("private") if self.elastic_ip.is_some() => Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "connectivity_type",
+                second: "allocation_id",
+            }),
+            None | Some("public") | Some("private") => Ok(()),
+            Some(other) => Err(Error::invalid(
+                Self::TYPE,
+                "connectivity_type",
+                format!("expected `public` or `private`, got `{}`", other),
+            )),
         }
+    }
+
+    /// The ID of the NAT Gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The association ID of the Elastic IP address that's associated with the NAT Gateway.
+    pub fn association_id(&self) -> Reference {
+        self.attr("association_id")
+    }
 
-        block.build()
+    /// The ID of the network interface associated with the NAT Gateway.
+    pub fn network_interface_id(&self) -> Reference {
+        self.attr("network_interface_id")
+    }
+
+    /// The public IP address of the NAT Gateway.
+    pub fn public_ip(&self) -> Reference {
+        self.attr("public_ip")
+    }
+
+    /// The private IP address of the NAT Gateway.
+    pub fn private_ip(&self) -> Reference {
+        self.attr("private_ip")
     }
 }
 
 /// Represents a data source for an AWS NAT Gateway.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_nat_gateway")]
 pub struct NATDataSource {
     /// One or more name-value pairs to filter by.
+    #[hcl(block)]
     pub filter: Option<Vec<Filter>>,
 
     /// The ID of the NAT Gateway.
@@ -94,107 +131,129 @@ pub struct NATDataSource {
     pub vpc_id: Option<String>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Filter {
-    pub name: String,
-    pub values: Vec<String>,
-}
-
-impl From<NATDataSource> for Block {
-    fn from(data_source: NATDataSource) -> Self {
-        let mut block = Block::builder("data")
-            .add_label("aws_nat_gateway")
-            .add_label(data_source.id.as_deref().unwrap_or("nat"));
-
-        if let Some(id) = data_source.id {
-            block = block.add_attribute(("id", Expression::String(id)));
-        }
-
-        if let Some(vpc_id) = data_source.vpc_id {
-            block = block.add_attribute(("vpc_id", Expression::String(vpc_id)));
-        }
-
-        if let Some(state) = data_source.state {
-            block = block.add_attribute(("state", Expression::String(state)));
-        }
+impl DataSource for NATDataSource {
+    const TYPE: &'static str = "aws_nat_gateway";
 
-        if let Some(tags) = data_source.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
+    fn name(&self) -> &str {
+        self.id.as_deref().unwrap_or("nat")
+    }
+}
 
-        if let Some(filters) = data_source.filter {
-            let filter_blocks: Vec<Block> = filters
-                .into_iter()
-                .map(|f| {
-                    Block::builder("filter")
-                        .add_attribute(("name", Expression::String(f.name)))
-                        .add_attribute((
-                            "values",
-                            Expression::Array(
-                                f.values.into_iter().map(Expression::String).collect(),
-                            ),
-                        ))
-                        .build()
-                })
-                .collect();
-            block = block.add_blocks(filter_blocks);
-        }
+impl NATDataSource {
+    /// The ID of the NAT Gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
 
-        block.build()
+    /// The public IP address of the NAT Gateway.
+    pub fn public_ip(&self) -> Reference {
+        self.attr("public_ip")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aws::network::gateway::internet::Internet;
+    use hcl::Block;
 
     #[test]
     fn test_nat_gateway_to_hcl() {
-        let vpc = Vpc {
-            name: "test-vpc".to_string(),
-            cidr_block: "10.0.0.0/16".to_string(),
-            instance_tenancy: None,
-            enable_dns_hostnames: None,
-            enable_dns_support: None,
-            enable_classiclink: None,
-            enable_classiclink_dns_support: None,
-            assign_generated_ipv6_cidr_block: None,
-            tags: None,
+        let vpc = Vpc::new("test-vpc", "10.0.0.0/16".parse().unwrap());
+
+        let subnet = Subnet {
+            map_public_ip_on_launch: Some(true.into()),
+            ..Subnet::new("public_subnet", &vpc, "10.0.1.0/24".parse().unwrap())
         };
 
-        let nat_gateway = Gateway {
+        let elastic_ip = ElasticIp::vpc("nat_eip");
+
+        let nat_gateway = NAT {
             id: Some("ngw-12345".to_string()),
-            subnet_id: "public_subnet".to_string(),
             vpc: &vpc,
-            allocation_id: Some("eipalloc-12345".to_string()),
-            connectivity_type: Some("public".to_string()),
-            tags: Some(HashMap::from([
-                ("Name".to_string(), "Main NAT Gateway".to_string()),
-                ("Environment".to_string(), "Production".to_string()),
-            ])),
+            subnet: &subnet,
+            elastic_ip: Some(&elastic_ip),
+            connectivity_type: Some("public".into()),
+            tags: Some(
+                HashMap::from([
+                    ("Name".to_string(), "Main NAT Gateway".to_string()),
+                    ("Environment".to_string(), "Production".to_string()),
+                ])
+                .into(),
+            ),
             state: None,
+            meta: MetaArguments::default(),
         };
 
-        let block: Block = nat_gateway.into();
+        let nat_gateway_clone = nat_gateway.clone();
+        let block = Block::try_from(nat_gateway).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
 
         assert!(hcl.contains(r#"resource "aws_nat_gateway" "ngw-12345""#));
         assert!(hcl.contains(r#"subnet_id = aws_subnet.public_subnet.id"#));
-        assert!(hcl.contains(r#"allocation_id = "eipalloc-12345""#));
+        assert!(hcl.contains(r#"allocation_id = aws_eip.nat_eip.allocation_id"#));
         assert!(hcl.contains(r#"connectivity_type = "public""#));
         assert!(hcl.contains(r#"tags = {"#));
         assert!(hcl.contains(r#""Name" = "Main NAT Gateway""#));
         assert!(hcl.contains(r#""Environment" = "Production""#));
+
+        let internet = Internet {
+            name: "main".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let dependent = NAT {
+            meta: MetaArguments {
+                depends_on: vec![internet.reference()],
+                ..Default::default()
+            },
+            ..nat_gateway_clone.clone()
+        };
+        let hcl = hcl::to_string(&Block::try_from(dependent).unwrap()).unwrap();
+        assert!(hcl.contains(
+            r#"depends_on = [
+    aws_internet_gateway.main
+  ]"#
+        ));
+
+        let without_eip = NAT {
+            elastic_ip: None,
+            ..nat_gateway_clone.clone()
+        };
+        assert!(matches!(
+            Block::try_from(without_eip.clone()),
+            Err(Error::MissingArgument {
+                argument: "allocation_id",
+                ..
+            })
+        ));
+
+        let private = NAT {
+            connectivity_type: Some("private".into()),
+            ..without_eip
+        };
+        let hcl = hcl::to_string(&Block::try_from(private).unwrap()).unwrap();
+        assert!(hcl.contains(r#"connectivity_type = "private""#));
+        assert!(!hcl.contains("allocation_id"));
+
+        let private_with_eip = NAT {
+            connectivity_type: Some("private".into()),
+            ..nat_gateway_clone
+        };
+        assert!(matches!(
+            Block::try_from(private_with_eip),
+            Err(Error::ConflictingArguments {
+                first: "connectivity_type",
+                second: "allocation_id",
+                ..
+            })
+        ));
     }
 
     #[test]
     fn test_nat_gateway_data_source_to_hcl() {
-        let data_source = NatGatewayDataSource {
+        let data_source = NATDataSource {
             id: Some("ngw-12345".to_string()),
             subnet_id: Some("subnet-12345".to_string()),
             vpc_id: Some("vpc-12345".to_string()),
@@ -0,0 +1,665 @@
+use crate::aws::network::cidr;
+use crate::aws::region::Region;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::{Block, Expression};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Hands out non-overlapping CIDR blocks from a pool and records them in a local JSON or
+/// TOML file, so VPCs created from separate configurations can still be peered or attached
+/// to one transit gateway.
+///
+/// Allocations are keyed by name and idempotent: asking again for `main` returns the block
+/// it already holds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allocator {
+    pool: cidr::Block,
+    allocations: BTreeMap<String, cidr::Block>,
+}
+
+impl Allocator {
+    pub fn new(pool: cidr::Block) -> Self {
+        Allocator {
+            pool: pool.normalized(),
+            allocations: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the allocations in `path`, or starts an empty allocator if the file does not
+    /// exist yet. Fails if the file was written for a different pool.
+    pub fn open<P: AsRef<Path>>(path: P, pool: cidr::Block) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new(pool));
+        }
+
+        let allocator = Self::load(path)?;
+        if allocator.pool != pool.normalized() {
+            return Err(Error::invalid(
+                "ipam",
+                "pool",
+                format!(
+                    "`{}` holds allocations from {}, not {}",
+                    path.display(),
+                    allocator.pool,
+                    pool
+                ),
+            ));
+        }
+        Ok(allocator)
+    }
+
+    /// Reads allocations from a `.json` or `.toml` file, checking that they fit the pool and
+    /// do not overlap.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let state_error = |reason: String| Error::StateFile {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let contents = fs::read_to_string(path).map_err(|e| state_error(e.to_string()))?;
+
+        let stored: Allocator = match Format::of(path)? {
+            Format::Json => serde_json::from_str(&contents).map_err(|e| state_error(e.to_string())),
+            Format::Toml => toml::from_str(&contents).map_err(|e| state_error(e.to_string())),
+        }?;
+
+        let mut allocator = Self::new(stored.pool);
+        for (name, block) in stored.allocations {
+            allocator.reserve(&name, block)?;
+        }
+        Ok(allocator)
+    }
+
+    /// Writes the allocations to a `.json` or `.toml` file. The file is written next to
+    /// `path` first and then renamed over it, so readers never see a partial file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let state_error = |reason: String| Error::StateFile {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let contents = match Format::of(path)? {
+            Format::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| state_error(e.to_string()))?
+            }
+            Format::Toml => toml::to_string(self).map_err(|e| state_error(e.to_string()))?,
+        };
+
+        let mut temp_name = OsString::from(".");
+        temp_name.push(path.file_name().unwrap_or_default());
+        temp_name.push(".tmp");
+        let temp = path.with_file_name(temp_name);
+
+        let written = fs::File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp, path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(state_error(e.to_string()));
+        }
+        Ok(())
+    }
+
+    /// The block allocations are taken from.
+    pub fn pool(&self) -> &cidr::Block {
+        &self.pool
+    }
+
+    /// Every allocation, by name.
+    pub fn allocations(&self) -> &BTreeMap<String, cidr::Block> {
+        &self.allocations
+    }
+
+    /// The block allocated to `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&cidr::Block> {
+        self.allocations.get(name)
+    }
+
+    /// Allocates the lowest free block of `prefix_length` to `name`, or returns the block
+    /// `name` already holds.
+    pub fn allocate(&mut self, name: &str, prefix_length: u8) -> Result<cidr::Block, Error> {
+        if let Some(existing) = self.allocations.get(name) {
+            if existing.prefix_length() == prefix_length {
+                return Ok(existing.clone());
+            }
+            return Err(Error::invalid(
+                "ipam",
+                "name",
+                format!("`{}` already holds {}", name, existing),
+            ));
+        }
+
+        if !(self.pool.prefix_length()..=32).contains(&prefix_length) {
+            return Err(Error::invalid(
+                "ipam",
+                "prefix_length",
+                format!("/{} does not fit in pool {}", prefix_length, self.pool),
+            ));
+        }
+
+        let base = u64::from(u32::from(self.pool.network_address()));
+        let end = base + self.pool.size();
+        let size = 1u64 << (32 - u32::from(prefix_length));
+        let taken: Vec<(u64, u64)> = self
+            .allocations
+            .values()
+            .map(|block| {
+                let start = u64::from(u32::from(block.network_address()));
+                (start, start + block.size())
+            })
+            .collect();
+
+        let mut next = base;
+        loop {
+            let start = next.div_ceil(size) * size;
+            if start + size > end {
+                return Err(Error::invalid(
+                    "ipam",
+                    "prefix_length",
+                    format!("pool {} has no free /{} left", self.pool, prefix_length),
+                ));
+            }
+            match taken
+                .iter()
+                .filter(|(taken_start, taken_end)| {
+                    *taken_start < start + size && start < *taken_end
+                })
+                .map(|(_, taken_end)| *taken_end)
+                .max()
+            {
+                Some(taken_end) => next = taken_end,
+                None => {
+                    let block = self.pool.nth_subnet(prefix_length, (start - base) / size)?;
+                    self.allocations.insert(name.to_string(), block.clone());
+                    return Ok(block);
+                }
+            }
+        }
+    }
+
+    /// Records an existing block, such as the CIDR of a VPC created before the allocator.
+    pub fn reserve(&mut self, name: &str, block: cidr::Block) -> Result<(), Error> {
+        let block = block.normalized();
+        if !block.is_subnet_of(&self.pool) {
+            return Err(Error::invalid(
+                "ipam",
+                "cidr",
+                format!("{} is outside pool {}", block, self.pool),
+            ));
+        }
+
+        for (other, taken) in &self.allocations {
+            if other == name && *taken == block {
+                return Ok(());
+            }
+            if other == name || taken.overlaps(&block) {
+                return Err(Error::invalid(
+                    "ipam",
+                    "cidr",
+                    format!(
+                        "{} for `{}` conflicts with {} held by `{}`",
+                        block, name, taken, other
+                    ),
+                ));
+            }
+        }
+
+        self.allocations.insert(name.to_string(), block);
+        Ok(())
+    }
+
+    /// Frees the block held by `name`, returning it.
+    pub fn release(&mut self, name: &str) -> Option<cidr::Block> {
+        self.allocations.remove(name)
+    }
+
+    /// Provisions the allocator's pool into an AWS IPAM pool, for teams that want AWS to
+    /// see the same ranges.
+    pub fn pool_cidr<'a>(&self, name: &str, pool: &'a IpamPool<'a>) -> IpamPoolCidr<'a> {
+        IpamPoolCidr {
+            name: name.to_string(),
+            pool,
+            cidr: self.pool.clone().into(),
+            meta: MetaArguments::default(),
+        }
+    }
+}
+
+/// File formats the allocator reads and writes, chosen by extension.
+enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self, Error> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(Format::Json),
+            Some("toml") => Ok(Format::Toml),
+            _ => Err(Error::invalid(
+                "ipam",
+                "path",
+                format!("`{}` is not a .json or .toml file", path.display()),
+            )),
+        }
+    }
+}
+
+/// A region an IPAM manages addresses in, rendered as an `operating_regions` block.
+#[derive(Debug, Clone)]
+pub struct OperatingRegion {
+    pub region_name: Value<Region>,
+}
+
+impl From<Region> for OperatingRegion {
+    fn from(region: Region) -> Self {
+        OperatingRegion {
+            region_name: region.into(),
+        }
+    }
+}
+
+impl From<OperatingRegion> for Block {
+    fn from(region: OperatingRegion) -> Self {
+        Block::builder("operating_regions")
+            .add_attribute(("region_name", region.region_name))
+            .build()
+    }
+}
+
+/// Represents an AWS VPC IPAM resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_ipam")]
+pub struct Ipam {
+    /// The name of the IPAM.
+    #[hcl(label)]
+    pub name: String,
+
+    /// A description of the IPAM.
+    pub description: Option<Value<String>>,
+
+    /// Regions the IPAM manages addresses in. Must include the provider's region.
+    #[hcl(block)]
+    pub operating_regions: Vec<OperatingRegion>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl Ipam {
+    /// Checks the IPAM arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.operating_regions.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "operating_regions",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the IPAM.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the IPAM.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the scope for private address space, created with the IPAM.
+    pub fn private_default_scope_id(&self) -> Reference {
+        self.attr("private_default_scope_id")
+    }
+
+    /// The ID of the scope for public address space, created with the IPAM.
+    pub fn public_default_scope_id(&self) -> Reference {
+        self.attr("public_default_scope_id")
+    }
+}
+
+/// IP version of an IPAM pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+impl fmt::Display for AddressFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressFamily::Ipv4 => write!(f, "ipv4"),
+            AddressFamily::Ipv6 => write!(f, "ipv6"),
+        }
+    }
+}
+
+impl IntoExpression for AddressFamily {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(AddressFamily);
+
+/// Represents an AWS VPC IPAM Pool resource in an IPAM's private scope.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_ipam_pool")]
+pub struct IpamPool<'a> {
+    /// The name of the pool.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The IPAM whose private scope holds the pool.
+    #[hcl(reference = "private_default_scope_id", rename = "ipam_scope_id")]
+    pub ipam: &'a Ipam,
+
+    /// The IP version of the pool.
+    pub address_family: Value<AddressFamily>,
+
+    /// The region VPCs can take addresses from the pool in.
+    pub locale: Option<Value<Region>>,
+
+    /// A description of the pool.
+    pub description: Option<Value<String>>,
+
+    /// Netmask length of allocations that do not ask for one.
+    pub allocation_default_netmask_length: Option<Value<u8>>,
+
+    /// Smallest netmask length, and so largest block, an allocation may ask for.
+    pub allocation_min_netmask_length: Option<Value<u8>>,
+
+    /// Largest netmask length, and so smallest block, an allocation may ask for.
+    pub allocation_max_netmask_length: Option<Value<u8>>,
+
+    /// Imports existing resources whose CIDRs fall in the pool.
+    pub auto_import: Option<Value<bool>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl IpamPool<'_> {
+    /// Checks the pool arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        let max = match self.address_family.as_literal() {
+            Some(AddressFamily::Ipv6) => 128,
+            _ => 32,
+        };
+        let lengths = [
+            (
+                "allocation_min_netmask_length",
+                &self.allocation_min_netmask_length,
+            ),
+            (
+                "allocation_default_netmask_length",
+                &self.allocation_default_netmask_length,
+            ),
+            (
+                "allocation_max_netmask_length",
+                &self.allocation_max_netmask_length,
+            ),
+        ];
+
+        let mut previous: Option<u8> = None;
+        for (argument, length) in lengths {
+            let Some(&length) = length.as_ref().and_then(Value::as_literal) else {
+                continue;
+            };
+            if length > max {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    argument,
+                    format!("must be at most {}, got {}", max, length),
+                ));
+            }
+            if previous.is_some_and(|previous| length < previous) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    argument,
+                    "netmask lengths must satisfy min <= default <= max",
+                ));
+            }
+            previous = Some(length);
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the pool.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the pool.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+/// Provisions a CIDR block into an IPAM pool.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_ipam_pool_cidr")]
+pub struct IpamPoolCidr<'a> {
+    /// The name of the pool CIDR.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The pool to provision the block into.
+    #[hcl(reference = "id", rename = "ipam_pool_id")]
+    pub pool: &'a IpamPool<'a>,
+
+    /// The block to provision.
+    pub cidr: Value<cidr::Block>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl IpamPoolCidr<'_> {
+    /// Checks the pool CIDR arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the pool CIDR.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::Stack;
+    use std::env;
+
+    fn pool() -> cidr::Block {
+        "10.0.0.0/8".parse().unwrap()
+    }
+
+    #[test]
+    fn test_allocator_hands_out_free_blocks() {
+        let mut allocator = Allocator::new(pool());
+        allocator
+            .reserve("legacy", "10.0.0.0/16".parse().unwrap())
+            .unwrap();
+
+        let eks = allocator.allocate("eks", 16).unwrap();
+        let data = allocator.allocate("data", 12).unwrap();
+        let tools = allocator.allocate("tools", 20).unwrap();
+
+        assert_eq!(eks.to_string(), "10.1.0.0/16");
+        assert_eq!(data.to_string(), "10.16.0.0/12");
+        assert_eq!(tools.to_string(), "10.2.0.0/20");
+        assert_eq!(allocator.allocate("eks", 16).unwrap(), eks);
+        assert!(allocator.allocate("eks", 20).is_err());
+        assert!(allocator
+            .reserve("overlap", "10.1.128.0/17".parse().unwrap())
+            .is_err());
+        assert!(allocator
+            .reserve("outside", "172.16.0.0/16".parse().unwrap())
+            .is_err());
+
+        assert_eq!(allocator.release("eks"), Some(eks.clone()));
+        assert_eq!(allocator.allocate("logs", 16).unwrap(), eks);
+
+        let mut small = Allocator::new("10.0.0.0/23".parse().unwrap());
+        small.allocate("a", 24).unwrap();
+        small.allocate("b", 24).unwrap();
+        assert!(matches!(
+            small.allocate("c", 24),
+            Err(Error::InvalidArgument {
+                argument: "prefix_length",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_allocator_persists_json_and_toml() {
+        let dir = env::temp_dir().join(format!("nexus7-ipam-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut allocator = Allocator::open(dir.join("missing.json"), pool()).unwrap();
+        allocator.allocate("eks", 16).unwrap();
+        allocator.allocate("data", 16).unwrap();
+
+        for file in ["ipam.json", "ipam.toml"] {
+            let path = dir.join(file);
+            allocator.save(&path).unwrap();
+            assert_eq!(Allocator::open(&path, pool()).unwrap(), allocator);
+            assert!(Allocator::open(&path, "172.16.0.0/12".parse().unwrap()).is_err());
+        }
+
+        let toml = fs::read_to_string(dir.join("ipam.toml")).unwrap();
+        assert!(toml.contains(r#"pool = "10.0.0.0/8""#));
+        assert!(toml.contains(r#"eks = "10.0.0.0/16""#));
+
+        fs::write(
+            dir.join("overlap.json"),
+            r#"{"pool":"10.0.0.0/8","allocations":{"a":"10.0.0.0/16","b":"10.0.0.0/24"}}"#,
+        )
+        .unwrap();
+        assert!(Allocator::load(dir.join("overlap.json")).is_err());
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        assert!(matches!(
+            Allocator::load(dir.join("broken.json")),
+            Err(Error::StateFile { .. })
+        ));
+        assert!(allocator.save(dir.join("ipam.yaml")).is_err());
+        assert!(!dir.join(".ipam.json.tmp").exists());
+
+        let missing = dir.join("missing").join("ipam.json");
+        for result in [
+            Allocator::load(&missing).map(|_| ()),
+            allocator.save(&missing),
+        ] {
+            match result {
+                Err(Error::StateFile { path, .. }) => assert_eq!(path, missing),
+                other => panic!("expected a state file error, got {:?}", other),
+            }
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_ipam_resources_to_hcl() {
+        let allocator = Allocator::new(pool());
+        let ipam = Ipam {
+            name: "main".to_string(),
+            description: None,
+            operating_regions: vec![Region::UsEast1.into(), Region::UsWest2.into()],
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let ipam_pool = IpamPool {
+            name: "private".to_string(),
+            ipam: &ipam,
+            address_family: AddressFamily::Ipv4.into(),
+            locale: Some(Region::UsEast1.into()),
+            description: None,
+            allocation_default_netmask_length: Some(16.into()),
+            allocation_min_netmask_length: Some(12.into()),
+            allocation_max_netmask_length: Some(24.into()),
+            auto_import: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let mut stack = Stack::new();
+        stack
+            .add(ipam.clone())
+            .unwrap()
+            .add(ipam_pool.clone())
+            .unwrap()
+            .add(allocator.pool_cidr("private", &ipam_pool))
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_vpc_ipam" "main""#));
+        assert_eq!(hcl.matches("operating_regions {").count(), 2);
+        assert!(hcl.contains(r#"region_name = "us-west-2""#));
+        assert!(hcl.contains("ipam_scope_id = aws_vpc_ipam.main.private_default_scope_id"));
+        assert!(hcl.contains(r#"address_family = "ipv4""#));
+        assert!(hcl.contains("ipam_pool_id = aws_vpc_ipam_pool.private.id"));
+        assert!(hcl.contains(r#"cidr = "10.0.0.0/8""#));
+
+        let inverted = IpamPool {
+            allocation_default_netmask_length: Some(8.into()),
+            ..ipam_pool
+        };
+        assert!(matches!(
+            Block::try_from(inverted),
+            Err(Error::InvalidArgument {
+                argument: "allocation_default_netmask_length",
+                ..
+            })
+        ));
+        let no_regions = Ipam {
+            operating_regions: Vec::new(),
+            ..ipam
+        };
+        assert!(Block::try_from(no_regions).is_err());
+    }
+}
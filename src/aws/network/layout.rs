@@ -0,0 +1,592 @@
+use crate::aws::availability_zone::AvailabilityZone;
+use crate::aws::network::cidr;
+use crate::aws::network::gateway::{Internet, NAT};
+use crate::aws::network::ipam::Allocator;
+use crate::aws::network::route::{
+    AssociationTarget, Route, RouteDestination, RouteTable, RouteTableAssociation, RouteTarget,
+};
+use crate::aws::network::subnet::Subnet;
+use crate::aws::network::vpc::{ElasticIp, Vpc};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::stack::Stack;
+use crate::value::Value;
+use std::collections::{HashMap, HashSet};
+use std::net::Ipv4Addr;
+
+/// How the subnets of a tier reach the internet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TierKind {
+    /// Routed to the internet gateway; instances get public IPs.
+    Public,
+    /// Routed out through a NAT gateway in a public tier.
+    Private,
+    /// No route out of the VPC.
+    Isolated,
+}
+
+/// A row of subnets, one per availability zone, sharing a prefix length and routing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tier {
+    /// Name of the tier, used in resource names such as `main_app_a`.
+    pub name: String,
+    pub kind: TierKind,
+    /// Prefix length of each subnet in the tier.
+    pub prefix_length: u8,
+}
+
+impl Tier {
+    pub fn public(name: &str, prefix_length: u8) -> Self {
+        Self::new(name, TierKind::Public, prefix_length)
+    }
+
+    pub fn private(name: &str, prefix_length: u8) -> Self {
+        Self::new(name, TierKind::Private, prefix_length)
+    }
+
+    pub fn isolated(name: &str, prefix_length: u8) -> Self {
+        Self::new(name, TierKind::Isolated, prefix_length)
+    }
+
+    fn new(name: &str, kind: TierKind, prefix_length: u8) -> Self {
+        Tier {
+            name: name.to_string(),
+            kind,
+            prefix_length,
+        }
+    }
+}
+
+/// Where private tiers get their NAT gateways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NatGateways {
+    /// One NAT gateway per zone, so a zone outage does not cut off the others.
+    #[default]
+    PerZone,
+    /// A single NAT gateway in the first zone, shared by every zone at lower cost.
+    Single,
+}
+
+/// A subnet allocated by a [`VpcLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedSubnet {
+    /// Resource name, `<layout>_<tier>_<zone suffix>`.
+    pub name: String,
+    /// Name of the tier the subnet belongs to.
+    pub tier: String,
+    pub kind: TierKind,
+    pub availability_zone: AvailabilityZone,
+    pub cidr_block: cidr::Block,
+}
+
+/// Describes a multi-AZ network: a VPC CIDR block, the zones to span and the tiers of
+/// subnets to create in each zone. [`NetworkBuilder::build`] allocates the subnet CIDR
+/// blocks and returns the resulting [`VpcLayout`].
+#[derive(Debug, Clone)]
+pub struct NetworkBuilder {
+    name: String,
+    cidr_block: cidr::Block,
+    zones: Vec<AvailabilityZone>,
+    tiers: Vec<Tier>,
+    nat_gateways: NatGateways,
+    tags: HashMap<String, String>,
+}
+
+impl NetworkBuilder {
+    pub fn new(name: &str, cidr_block: cidr::Block) -> Self {
+        NetworkBuilder {
+            name: name.to_string(),
+            cidr_block: cidr_block.normalized(),
+            zones: Vec::new(),
+            tiers: Vec::new(),
+            nat_gateways: NatGateways::default(),
+            tags: HashMap::new(),
+        }
+    }
+
+    /// The zones to create each tier's subnets in, in order.
+    pub fn zones<I: IntoIterator<Item = AvailabilityZone>>(mut self, zones: I) -> Self {
+        self.zones.extend(zones);
+        self
+    }
+
+    /// Adds a tier. Tiers are allocated CIDR blocks in the order they are added.
+    pub fn tier(mut self, tier: Tier) -> Self {
+        self.tiers.push(tier);
+        self
+    }
+
+    pub fn nat_gateways(mut self, nat_gateways: NatGateways) -> Self {
+        self.nat_gateways = nat_gateways;
+        self
+    }
+
+    /// Tags added to every resource, alongside a `Name` tag.
+    pub fn tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// Checks the layout and allocates each subnet the lowest free block of its tier's
+    /// prefix length, tier by tier and zone by zone. Smaller tiers fill the gaps left by
+    /// aligning larger ones, as [`Allocator::allocate`] does.
+    pub fn build(self) -> Result<VpcLayout, Error> {
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Vpc::TYPE,
+                argument: "name",
+            });
+        }
+
+        let Some(first_zone) = self.zones.first() else {
+            return Err(Error::MissingArgument {
+                resource: Subnet::TYPE,
+                argument: "availability_zone",
+            });
+        };
+        let mut zones = HashSet::new();
+        for zone in &self.zones {
+            if zone.region() != first_zone.region() {
+                return Err(Error::invalid(
+                    Subnet::TYPE,
+                    "availability_zone",
+                    format!("`{}` is not in region `{}`", zone, first_zone.region()),
+                ));
+            }
+            if !zones.insert(zone) {
+                return Err(Error::invalid(
+                    Subnet::TYPE,
+                    "availability_zone",
+                    format!("`{}` is listed twice", zone),
+                ));
+            }
+        }
+
+        let mut names = HashSet::new();
+        for tier in &self.tiers {
+            if tier.name.is_empty() || !names.insert(tier.name.as_str()) {
+                return Err(Error::invalid(
+                    Subnet::TYPE,
+                    "name",
+                    format!("tier name `{}` is empty or used twice", tier.name),
+                ));
+            }
+            if !(16..=28).contains(&tier.prefix_length) {
+                return Err(Error::invalid(
+                    Subnet::TYPE,
+                    "cidr_block",
+                    format!(
+                        "tier `{}` prefix length must be between /16 and /28, got /{}",
+                        tier.name, tier.prefix_length
+                    ),
+                ));
+            }
+        }
+        if self.tiers.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Subnet::TYPE,
+                argument: "cidr_block",
+            });
+        }
+
+        let has_public = self.tiers.iter().any(|t| t.kind == TierKind::Public);
+        if let Some(tier) = self.tiers.iter().find(|t| t.kind == TierKind::Private) {
+            if !has_public {
+                return Err(Error::invalid(
+                    NAT::TYPE,
+                    "subnet_id",
+                    format!(
+                        "private tier `{}` needs a public tier to hold its NAT gateway",
+                        tier.name
+                    ),
+                ));
+            }
+        }
+
+        let subnets = self.allocate()?;
+
+        Ok(VpcLayout {
+            name: self.name,
+            cidr_block: self.cidr_block,
+            zones: self.zones,
+            tiers: self.tiers,
+            nat_gateways: self.nat_gateways,
+            tags: self.tags,
+            subnets,
+        })
+    }
+
+    fn allocate(&self) -> Result<Vec<PlannedSubnet>, Error> {
+        let mut allocator = Allocator::new(self.cidr_block.clone());
+        let mut subnets = Vec::new();
+
+        for tier in &self.tiers {
+            for zone in &self.zones {
+                let name = format!("{}_{}_{}", self.name, tier.name, zone.suffix());
+                let cidr_block = allocator.allocate(&name, tier.prefix_length).map_err(|_| {
+                    Error::invalid(
+                        Vpc::TYPE,
+                        "cidr_block",
+                        format!(
+                            "{} has no room for a /{} in tier `{}` of `{}`",
+                            self.cidr_block, tier.prefix_length, tier.name, zone
+                        ),
+                    )
+                })?;
+
+                subnets.push(PlannedSubnet {
+                    name,
+                    tier: tier.name.clone(),
+                    kind: tier.kind,
+                    availability_zone: zone.clone(),
+                    cidr_block,
+                });
+            }
+        }
+
+        Ok(subnets)
+    }
+}
+
+/// A multi-AZ network with its subnet CIDR blocks allocated, built by [`NetworkBuilder`].
+///
+/// [`VpcLayout::add_to`] emits the VPC, subnets, internet gateway, Elastic IPs, NAT
+/// gateways, route tables, routes and associations. Public tiers share one route table
+/// per tier; private tiers get one per zone with [`NatGateways::PerZone`].
+#[derive(Debug, Clone)]
+pub struct VpcLayout {
+    name: String,
+    cidr_block: cidr::Block,
+    zones: Vec<AvailabilityZone>,
+    tiers: Vec<Tier>,
+    nat_gateways: NatGateways,
+    tags: HashMap<String, String>,
+    subnets: Vec<PlannedSubnet>,
+}
+
+impl VpcLayout {
+    pub fn builder(name: &str, cidr_block: cidr::Block) -> NetworkBuilder {
+        NetworkBuilder::new(name, cidr_block)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cidr_block(&self) -> &cidr::Block {
+        &self.cidr_block
+    }
+
+    /// Every subnet, tier by tier and zone by zone.
+    pub fn subnets(&self) -> &[PlannedSubnet] {
+        &self.subnets
+    }
+
+    /// The subnets of `tier`, one per zone.
+    pub fn tier_subnets<'a>(&'a self, tier: &'a str) -> impl Iterator<Item = &'a PlannedSubnet> {
+        self.subnets
+            .iter()
+            .filter(move |subnet| subnet.tier == tier)
+    }
+
+    /// The ID of the VPC.
+    pub fn vpc_id(&self) -> Reference {
+        Reference::resource(Vpc::TYPE, &self.name).attr("id")
+    }
+
+    /// The IDs of the subnets of `tier`, e.g. for an EKS cluster or load balancer.
+    pub fn subnet_ids(&self, tier: &str) -> Vec<Reference> {
+        self.tier_subnets(tier)
+            .map(|subnet| Reference::resource(Subnet::TYPE, &subnet.name).attr("id"))
+            .collect()
+    }
+
+    /// Adds every resource of the network to `stack`.
+    pub fn add_to<'s>(&self, stack: &'s mut Stack) -> Result<&'s mut Stack, Error> {
+        let vpc = Vpc {
+            enable_dns_hostnames: Some(true.into()),
+            enable_dns_support: Some(true.into()),
+            tags: self.tags(&self.name),
+            ..Vpc::new(&self.name, self.cidr_block.clone())
+        };
+        stack.add(vpc.clone())?;
+
+        let subnets: Vec<Subnet> = self
+            .subnets
+            .iter()
+            .map(|planned| Subnet {
+                availability_zone: Some(planned.availability_zone.clone().into()),
+                map_public_ip_on_launch: (planned.kind == TierKind::Public).then(|| true.into()),
+                tags: self.tags(&planned.name),
+                ..Subnet::new(&planned.name, &vpc, planned.cidr_block.clone())
+            })
+            .collect();
+        for subnet in &subnets {
+            stack.add(subnet.clone())?;
+        }
+
+        let internet = self
+            .tiers
+            .iter()
+            .any(|tier| tier.kind == TierKind::Public)
+            .then(|| Internet {
+                name: self.name.clone(),
+                vpc: &vpc,
+                tags: self.tags(&self.name),
+                meta: MetaArguments::default(),
+            });
+        if let Some(internet) = &internet {
+            stack.add(internet.clone())?;
+        }
+
+        // NAT gateways sit in the first public tier, one per zone or only in the first.
+        let nat_zones = match self.nat_gateways {
+            _ if !self.tiers.iter().any(|t| t.kind == TierKind::Private) => &self.zones[..0],
+            NatGateways::PerZone => &self.zones[..],
+            NatGateways::Single => &self.zones[..1],
+        };
+        let nat_names: Vec<String> = nat_zones
+            .iter()
+            .map(|zone| match self.nat_gateways {
+                NatGateways::PerZone => format!("{}_{}", self.name, zone.suffix()),
+                NatGateways::Single => self.name.clone(),
+            })
+            .collect();
+        let elastic_ips: Vec<ElasticIp> = nat_names
+            .iter()
+            .map(|name| ElasticIp {
+                tags: self.tags(&format!("{}_nat", name)),
+                ..ElasticIp::vpc(&format!("{}_nat", name))
+            })
+            .collect();
+        for elastic_ip in &elastic_ips {
+            stack.add(elastic_ip.clone())?;
+        }
+
+        let nats: Vec<NAT> = nat_zones
+            .iter()
+            .zip(&nat_names)
+            .zip(&elastic_ips)
+            .map(|((zone, name), elastic_ip)| {
+                let subnet = self
+                    .subnets
+                    .iter()
+                    .position(|s| s.kind == TierKind::Public && &s.availability_zone == zone)
+                    .map(|index| &subnets[index])
+                    .expect("a public tier spans every zone");
+                NAT {
+                    id: Some(name.clone()),
+                    vpc: &vpc,
+                    subnet,
+                    elastic_ip: Some(elastic_ip),
+                    connectivity_type: None,
+                    tags: self.tags(name),
+                    state: None,
+                    meta: MetaArguments {
+                        depends_on: internet.iter().map(Resource::reference).collect(),
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+        for nat in &nats {
+            stack.add(nat.clone())?;
+        }
+
+        // One route table per public or isolated tier, and per zone for private tiers with
+        // a NAT gateway in each zone. `table_of[i]` is the table of `self.subnets[i]`.
+        let mut tables = Vec::new();
+        let mut targets = Vec::new();
+        let mut table_of = Vec::new();
+        for planned in &self.subnets {
+            let per_zone =
+                planned.kind == TierKind::Private && self.nat_gateways == NatGateways::PerZone;
+            let name = if per_zone {
+                planned.name.clone()
+            } else {
+                format!("{}_{}", self.name, planned.tier)
+            };
+
+            if let Some(index) = tables.iter().position(|t: &RouteTable| t.name == name) {
+                table_of.push(index);
+                continue;
+            }
+
+            let target = match planned.kind {
+                TierKind::Public => internet.as_ref().map(RouteTarget::from),
+                TierKind::Private if per_zone => nat_zones
+                    .iter()
+                    .position(|zone| zone == &planned.availability_zone)
+                    .map(|index| RouteTarget::from(&nats[index])),
+                TierKind::Private => nats.first().map(RouteTarget::from),
+                TierKind::Isolated => None,
+            };
+            table_of.push(tables.len());
+            targets.push(target);
+            tables.push(RouteTable {
+                name: name.clone(),
+                vpc: &vpc,
+                propagating_vgws: None,
+                tags: self.tags(&name),
+                meta: MetaArguments::default(),
+            });
+        }
+        for table in &tables {
+            stack.add(table.clone())?;
+        }
+
+        let default_route = cidr::Block::new(Ipv4Addr::UNSPECIFIED, 0)?;
+        for (table, target) in tables.iter().zip(targets) {
+            if let Some(target) = target {
+                stack.add(Route {
+                    name: format!("{}_default", table.name),
+                    route_table: table,
+                    destination: RouteDestination::Ipv4(default_route.clone().into()),
+                    target,
+                    meta: MetaArguments::default(),
+                })?;
+            }
+        }
+
+        for (subnet, index) in subnets.iter().zip(table_of) {
+            stack.add(RouteTableAssociation {
+                name: subnet.name.clone(),
+                target: AssociationTarget::from(subnet),
+                route_table: &tables[index],
+                meta: MetaArguments::default(),
+            })?;
+        }
+
+        Ok(stack)
+    }
+
+    /// The common tags plus a `Name` tag.
+    fn tags(&self, name: &str) -> Option<Value<HashMap<String, String>>> {
+        let mut tags = self.tags.clone();
+        tags.insert("Name".to_string(), name.to_string());
+        Some(tags.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::region::Region;
+
+    fn zones() -> Vec<AvailabilityZone> {
+        Region::UsEast1.availability_zones()[..3].to_vec()
+    }
+
+    fn layout(nat_gateways: NatGateways) -> VpcLayout {
+        VpcLayout::builder("main", "10.0.0.0/16".parse().unwrap())
+            .zones(zones())
+            .tier(Tier::public("public", 24))
+            .tier(Tier::private("app", 20))
+            .tier(Tier::isolated("data", 24))
+            .nat_gateways(nat_gateways)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_layout_allocates_subnets() {
+        let layout = layout(NatGateways::PerZone);
+        let cidrs: Vec<String> = layout
+            .subnets()
+            .iter()
+            .map(|s| format!("{} {}", s.name, s.cidr_block))
+            .collect();
+
+        assert_eq!(
+            cidrs,
+            [
+                "main_public_a 10.0.0.0/24",
+                "main_public_b 10.0.1.0/24",
+                "main_public_c 10.0.2.0/24",
+                "main_app_a 10.0.16.0/20",
+                "main_app_b 10.0.32.0/20",
+                "main_app_c 10.0.48.0/20",
+                "main_data_a 10.0.3.0/24",
+                "main_data_b 10.0.4.0/24",
+                "main_data_c 10.0.5.0/24",
+            ]
+        );
+        assert_eq!(layout.subnet_ids("app").len(), 3);
+        assert_eq!(
+            layout.subnet_ids("data")[0],
+            Reference::resource("aws_subnet", "main_data_a").attr("id")
+        );
+    }
+
+    #[test]
+    fn test_layout_emits_nat_per_zone() {
+        let mut stack = Stack::new();
+        layout(NatGateways::PerZone).add_to(&mut stack).unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_vpc" "main""#));
+        assert!(hcl.contains(r#"resource "aws_internet_gateway" "main""#));
+        assert_eq!(hcl.matches(r#"resource "aws_nat_gateway""#).count(), 3);
+        assert_eq!(hcl.matches(r#"resource "aws_eip""#).count(), 3);
+        assert!(hcl.contains(r#"resource "aws_nat_gateway" "main_b""#));
+        assert!(hcl.contains("subnet_id = aws_subnet.main_public_b.id"));
+        assert_eq!(hcl.matches(r#"resource "aws_route_table""#).count(), 5);
+        assert_eq!(hcl.matches(r#"resource "aws_route""#).count(), 4);
+        assert_eq!(
+            hcl.matches(r#"resource "aws_route_table_association""#)
+                .count(),
+            9
+        );
+        assert!(hcl.contains(r#"resource "aws_route" "main_app_c_default""#));
+        assert!(hcl.contains("nat_gateway_id = aws_nat_gateway.main_c.id"));
+        assert!(hcl.contains("gateway_id = aws_internet_gateway.main.id"));
+        assert!(hcl.contains("route_table_id = aws_route_table.main_data.id"));
+        assert!(!hcl.contains(r#"resource "aws_route" "main_data_default""#));
+    }
+
+    #[test]
+    fn test_layout_emits_single_nat() {
+        let mut stack = Stack::new();
+        layout(NatGateways::Single).add_to(&mut stack).unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert_eq!(hcl.matches(r#"resource "aws_nat_gateway""#).count(), 1);
+        assert!(hcl.contains("subnet_id = aws_subnet.main_public_a.id"));
+        assert!(hcl.contains(r#"resource "aws_route" "main_app_default""#));
+        assert_eq!(hcl.matches(r#"resource "aws_route_table""#).count(), 3);
+    }
+
+    #[test]
+    fn test_layout_rejects_invalid_layouts() {
+        let too_small = VpcLayout::builder("main", "10.0.0.0/22".parse().unwrap())
+            .zones(zones())
+            .tier(Tier::public("public", 24))
+            .tier(Tier::private("app", 24))
+            .build();
+        assert!(matches!(
+            too_small,
+            Err(Error::InvalidArgument {
+                argument: "cidr_block",
+                ..
+            })
+        ));
+
+        let no_public = VpcLayout::builder("main", "10.0.0.0/16".parse().unwrap())
+            .zones(zones())
+            .tier(Tier::private("app", 24))
+            .build();
+        assert!(matches!(
+            no_public,
+            Err(Error::InvalidArgument {
+                argument: "subnet_id",
+                ..
+            })
+        ));
+
+        let no_zones = VpcLayout::builder("main", "10.0.0.0/16".parse().unwrap())
+            .tier(Tier::public("public", 24))
+            .build();
+        assert!(matches!(no_zones, Err(Error::MissingArgument { .. })));
+    }
+}
@@ -1,4 +1,17 @@
 pub mod cidr;
+pub mod dhcp;
+pub mod endpoint;
+pub mod flow_log;
 pub mod gateway;
+pub mod ipam;
+pub mod layout;
+pub mod network_acl;
+pub mod peering;
+pub mod protocol;
+pub mod resolver;
+pub mod route;
+pub mod security_group;
 pub mod subnet;
+pub mod transit_gateway;
+pub mod validation;
 pub mod vpc;
@@ -0,0 +1,382 @@
+use crate::aws::network::cidr;
+pub use crate::aws::network::protocol::{IcmpFilter, PortRange, Protocol};
+use crate::aws::network::subnet::Subnet;
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Rule numbers accepted by AWS. Higher numbers are reserved for the default rules.
+const RULE_NUMBERS: std::ops::RangeInclusive<u16> = 1..=32766;
+
+/// Whether a network ACL rule lets matching traffic through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleAction::Allow => write!(f, "allow"),
+            RuleAction::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl IntoExpression for RuleAction {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(RuleAction);
+
+/// Represents an AWS Network ACL resource. Rules and subnets are added with separate
+/// [`NetworkAclRule`] and [`NetworkAclAssociation`] resources.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_network_acl")]
+pub struct NetworkAcl<'a> {
+    /// The name of the network ACL.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC the network ACL belongs to.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl NetworkAcl<'_> {
+    /// Checks the network ACL arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no two rules of this network ACL share a rule number in the same
+    /// direction. Rules of other network ACLs are ignored. [`NetworkResources`] reports the
+    /// same duplicates for every listed rule, along with its other checks.
+    ///
+    /// [`NetworkResources`]: crate::aws::network::validation::NetworkResources
+    pub fn validate_rules(&self, rules: &[NetworkAclRule<'_>]) -> Result<(), Error> {
+        let rules: Vec<_> = rules
+            .iter()
+            .filter(|rule| rule.network_acl.name == self.name)
+            .filter_map(|rule| Some((rule.egress, *rule.rule_number.as_literal()?)))
+            .collect();
+
+        for (i, (egress, number)) in rules.iter().enumerate() {
+            if rules[..i].contains(&(*egress, *number)) {
+                return Err(Error::invalid(
+                    NetworkAclRule::TYPE,
+                    "rule_number",
+                    format!(
+                        "{} rule number {} is used more than once in `{}`",
+                        if *egress { "egress" } else { "ingress" },
+                        number,
+                        self.name
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the network ACL.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the network ACL.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the AWS account that owns the network ACL.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+}
+
+/// Represents an entry in an AWS Network ACL. Rules are evaluated in ascending
+/// `rule_number` order and the first match applies.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_network_acl_rule")]
+pub struct NetworkAclRule<'a> {
+    /// The name of the rule.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The network ACL the rule belongs to.
+    #[hcl(reference = "id")]
+    pub network_acl: &'a NetworkAcl<'a>,
+
+    /// The position of the rule, from 1 to 32766.
+    pub rule_number: Value<u16>,
+
+    /// Whether the rule applies to outbound rather than inbound traffic.
+    pub egress: bool,
+
+    /// The protocol and ports the rule matches.
+    #[hcl(flatten = "apply_to_acl_rule")]
+    pub protocol: Protocol,
+
+    /// Whether matching traffic is allowed or denied.
+    pub rule_action: Value<RuleAction>,
+
+    /// The IPv4 CIDR block the rule matches. Conflicts with `ipv6_cidr_block`.
+    pub cidr_block: Option<Value<cidr::Block>>,
+
+    /// The IPv6 CIDR block the rule matches. Conflicts with `cidr_block`, and required for
+    /// ICMPv6 rules.
+    pub ipv6_cidr_block: Option<Value<cidr::Ipv6Block>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl NetworkAclRule<'_> {
+    /// Checks the rule arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        self.protocol.validate(Self::TYPE, "protocol")?;
+
+        if let Some(rule_number) = self.rule_number.as_literal() {
+            if !RULE_NUMBERS.contains(rule_number) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "rule_number",
+                    format!("must be between 1 and 32766, got {}", rule_number),
+                ));
+            }
+        }
+
+        match (&self.cidr_block, &self.ipv6_cidr_block) {
+            (Some(_), Some(_)) => Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "cidr_block",
+                second: "ipv6_cidr_block",
+            }),
+            (None, None) => Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "cidr_block",
+            }),
+            (Some(_), None) if matches!(self.protocol, Protocol::Icmpv6(_)) => Err(Error::invalid(
+                Self::TYPE,
+                "protocol",
+                "ICMPv6 rules must match an `ipv6_cidr_block`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The ID of the rule.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Associates a subnet with an AWS Network ACL, replacing its current association.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_network_acl_association")]
+pub struct NetworkAclAssociation<'a> {
+    /// The name of the association.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The network ACL to associate.
+    #[hcl(reference = "id")]
+    pub network_acl: &'a NetworkAcl<'a>,
+
+    /// The subnet to associate.
+    #[hcl(reference = "id")]
+    pub subnet: &'a Subnet<'a>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl NetworkAclAssociation<'_> {
+    /// Checks the association arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)
+    }
+
+    /// The ID of the association.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::validation::NetworkResources;
+    use hcl::Block;
+    use std::net::Ipv4Addr;
+
+    fn rule<'a>(acl: &'a NetworkAcl<'a>, name: &str, number: u16) -> NetworkAclRule<'a> {
+        NetworkAclRule {
+            name: name.to_string(),
+            network_acl: acl,
+            rule_number: number.into(),
+            egress: false,
+            protocol: Protocol::Tcp(PortRange::single(5432)),
+            rule_action: RuleAction::Allow.into(),
+            cidr_block: Some(
+                cidr::Block::new(Ipv4Addr::new(10, 0, 0, 0), 16)
+                    .unwrap()
+                    .into(),
+            ),
+            ipv6_cidr_block: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    #[test]
+    fn test_network_acl_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let subnet = Subnet::new("data", &vpc, "10.0.20.0/24".parse().unwrap());
+        let acl = NetworkAcl {
+            name: "data".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(acl.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_network_acl" "data""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+
+        let hcl = hcl::to_string(&Block::try_from(rule(&acl, "postgres", 100)).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_network_acl_rule" "postgres""#));
+        assert!(hcl.contains("network_acl_id = aws_network_acl.data.id"));
+        assert!(hcl.contains("rule_number = 100"));
+        assert!(hcl.contains("egress = false"));
+        assert!(hcl.contains(r#"protocol = "tcp""#));
+        assert!(hcl.contains("from_port = 5432"));
+        assert!(hcl.contains(r#"rule_action = "allow""#));
+        assert!(hcl.contains(r#"cidr_block = "10.0.0.0/16""#));
+
+        let ping = NetworkAclRule {
+            protocol: Protocol::Icmp(IcmpFilter::default()),
+            rule_action: RuleAction::Deny.into(),
+            ..rule(&acl, "ping", 200)
+        };
+        let hcl = hcl::to_string(&Block::try_from(ping).unwrap()).unwrap();
+        assert!(hcl.contains(r#"protocol = "icmp""#));
+        assert!(hcl.contains("icmp_type = -1"));
+        assert!(hcl.contains(r#"rule_action = "deny""#));
+
+        let ping6 = NetworkAclRule {
+            protocol: Protocol::Icmpv6(IcmpFilter::default()),
+            ..rule(&acl, "ping6", 210)
+        };
+        assert!(matches!(
+            Block::try_from(ping6.clone()),
+            Err(Error::InvalidArgument {
+                argument: "protocol",
+                ..
+            })
+        ));
+        let ping6 = NetworkAclRule {
+            cidr_block: None,
+            ipv6_cidr_block: Some("2001:db8::/56".parse::<cidr::Ipv6Block>().unwrap().into()),
+            ..ping6
+        };
+        let hcl = hcl::to_string(&Block::try_from(ping6.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"protocol = "58""#));
+        assert!(hcl.contains(r#"ipv6_cidr_block = "2001:db8::/56""#));
+        assert!(!hcl.contains("  cidr_block"));
+        assert!(matches!(
+            Block::try_from(NetworkAclRule {
+                cidr_block: rule(&acl, "any", 1).cidr_block,
+                ..ping6
+            }),
+            Err(Error::ConflictingArguments {
+                first: "cidr_block",
+                second: "ipv6_cidr_block",
+                ..
+            })
+        ));
+
+        let association = NetworkAclAssociation {
+            name: "data".to_string(),
+            network_acl: &acl,
+            subnet: &subnet,
+            meta: MetaArguments::default(),
+        };
+        let hcl = hcl::to_string(&Block::try_from(association).unwrap()).unwrap();
+        assert!(hcl.contains("subnet_id = aws_subnet.data.id"));
+    }
+
+    #[test]
+    fn test_network_acl_rule_numbers() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let acl = NetworkAcl {
+            name: "data".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        assert!(Block::try_from(rule(&acl, "zero", 0)).is_err());
+        assert!(Block::try_from(rule(&acl, "reserved", 32767)).is_err());
+
+        let egress = NetworkAclRule {
+            egress: true,
+            ..rule(&acl, "egress", 100)
+        };
+        let rules = vec![rule(&acl, "first", 100), egress, rule(&acl, "second", 110)];
+        assert!(acl.validate_rules(&rules).is_ok());
+
+        let rules = vec![rule(&acl, "first", 100), rule(&acl, "second", 100)];
+        assert!(matches!(
+            acl.validate_rules(&rules),
+            Err(Error::InvalidArgument {
+                argument: "rule_number",
+                ..
+            })
+        ));
+
+        let (first, second) = (rule(&acl, "first", 100), rule(&acl, "second", 100));
+        let violations = NetworkResources {
+            network_acl_rules: vec![&first, &second],
+            ..Default::default()
+        }
+        .violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].resources,
+            ["aws_network_acl_rule.first", "aws_network_acl_rule.second"]
+        );
+        assert_eq!(
+            violations[0].reason,
+            "ingress rule number 100 is used more than once in aws_network_acl.data"
+        );
+    }
+}
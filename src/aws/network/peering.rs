@@ -0,0 +1,307 @@
+use crate::aws::network::route::RouteTarget;
+use crate::aws::network::vpc::Vpc;
+use crate::aws::provider::AwsProvider;
+use crate::aws::region::Region;
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::provider::Provider;
+use crate::reference::{Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use hcl::Block;
+use std::collections::HashMap;
+
+/// Settings for one side of a peering connection, rendered as the `accepter` or
+/// `requester` block.
+#[derive(Debug, Clone, Default)]
+pub struct PeeringOptions {
+    /// Lets this side resolve public DNS host names of the other VPC to private IP addresses.
+    pub allow_remote_vpc_dns_resolution: Option<Value<bool>>,
+}
+
+impl From<PeeringOptions> for Block {
+    fn from(options: PeeringOptions) -> Self {
+        let mut block = Block::builder("options");
+
+        if let Some(allow) = options.allow_remote_vpc_dns_resolution {
+            block = block.add_attribute(("allow_remote_vpc_dns_resolution", allow));
+        }
+
+        block.build()
+    }
+}
+
+/// Represents an AWS VPC Peering Connection resource, created from the requester VPC.
+///
+/// Peering with a VPC in another region or account is not accepted automatically; accept
+/// it with [`VpcPeeringConnection::accept_with`] and the peer's provider configuration.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_peering_connection")]
+pub struct VpcPeeringConnection<'a> {
+    /// The name of the peering connection.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The requester VPC.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// The accepter VPC.
+    #[hcl(reference = "id")]
+    pub peer_vpc: &'a Vpc,
+
+    /// The AWS account that owns the accepter VPC, when it is not the requester's account.
+    pub peer_owner_id: Option<Value<String>>,
+
+    /// The region of the accepter VPC, when it is not the provider's region.
+    pub peer_region: Option<Value<Region>>,
+
+    /// Accepts the connection, for peering within one account and region.
+    pub auto_accept: Option<Value<bool>>,
+
+    /// Options for the accepter side.
+    #[hcl(block = "accepter")]
+    pub accepter: Option<PeeringOptions>,
+
+    /// Options for the requester side.
+    #[hcl(block = "requester")]
+    pub requester: Option<PeeringOptions>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl<'a> VpcPeeringConnection<'a> {
+    /// Checks the peering arguments against the constraints enforced by AWS, including that
+    /// the two VPCs' CIDR blocks do not overlap.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if self.auto_accept.is_some() && self.peer_region.is_some() {
+            return Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "auto_accept",
+                second: "peer_region",
+            });
+        }
+
+        if let (Some(cidr), Some(peer_cidr)) = (
+            self.vpc.cidr_block.as_literal(),
+            self.peer_vpc.cidr_block.as_literal(),
+        ) {
+            if cidr.overlaps(peer_cidr) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "peer_vpc_id",
+                    format!(
+                        "`{}` CIDR block {} overlaps `{}` CIDR block {}",
+                        self.vpc.name, cidr, self.peer_vpc.name, peer_cidr
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Accepts the connection in the peer VPC's region or account, using `provider` as the
+    /// accepter side's provider configuration.
+    pub fn accept_with(
+        &'a self,
+        name: &str,
+        provider: &AwsProvider,
+    ) -> VpcPeeringConnectionAccepter<'a> {
+        VpcPeeringConnectionAccepter {
+            name: name.to_string(),
+            peering_connection: self,
+            auto_accept: Some(true.into()),
+            accepter: None,
+            requester: None,
+            tags: None,
+            meta: MetaArguments {
+                provider: Some(provider.handle()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The ID of the peering connection.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The status of the peering connection, such as `active` or `pending-acceptance`.
+    pub fn accept_status(&self) -> Reference {
+        self.attr("accept_status")
+    }
+}
+
+impl From<&VpcPeeringConnection<'_>> for RouteTarget {
+    fn from(connection: &VpcPeeringConnection<'_>) -> Self {
+        RouteTarget::VpcPeeringConnection(connection.id().into())
+    }
+}
+
+/// Accepts a VPC Peering Connection from the accepter's side.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_peering_connection_accepter")]
+pub struct VpcPeeringConnectionAccepter<'a> {
+    /// The name of the accepter.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The peering connection to accept.
+    #[hcl(reference = "id", rename = "vpc_peering_connection_id")]
+    pub peering_connection: &'a VpcPeeringConnection<'a>,
+
+    /// Accepts the connection.
+    pub auto_accept: Option<Value<bool>>,
+
+    /// Options for the accepter side.
+    #[hcl(block = "accepter")]
+    pub accepter: Option<PeeringOptions>,
+
+    /// Options for the requester side.
+    #[hcl(block = "requester")]
+    pub requester: Option<PeeringOptions>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`. Set `provider` to the
+    /// accepter's provider configuration.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl VpcPeeringConnectionAccepter<'_> {
+    /// Checks the accepter arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the peering connection.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::cidr;
+    use crate::stack::Stack;
+    use std::net::Ipv4Addr;
+
+    fn vpc(name: &str, second_octet: u8) -> Vpc {
+        Vpc::new(
+            name,
+            cidr::Block::new(Ipv4Addr::new(10, second_octet, 0, 0), 16).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_cross_region_peering_to_hcl() {
+        let eks = vpc("eks", 0);
+        let data = vpc("data", 1);
+        let west = AwsProvider {
+            alias: Some("west".to_string()),
+            ..AwsProvider::new(Region::UsWest2)
+        };
+
+        let peering = VpcPeeringConnection {
+            name: "eks_data".to_string(),
+            vpc: &eks,
+            peer_vpc: &data,
+            peer_owner_id: None,
+            peer_region: Some(Region::UsWest2.into()),
+            auto_accept: None,
+            accepter: None,
+            requester: Some(PeeringOptions {
+                allow_remote_vpc_dns_resolution: Some(true.into()),
+            }),
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let accepter = peering.accept_with("eks_data", &west);
+
+        let mut stack = Stack::new();
+        stack
+            .add_provider(AwsProvider::new(Region::UsEast1))
+            .unwrap()
+            .add_provider(west.clone())
+            .unwrap()
+            .add(peering.clone())
+            .unwrap()
+            .add(accepter)
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_vpc_peering_connection" "eks_data""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.eks.id"));
+        assert!(hcl.contains("peer_vpc_id = aws_vpc.data.id"));
+        assert!(hcl.contains(r#"peer_region = "us-west-2""#));
+        assert!(hcl.contains("requester {"));
+        assert!(hcl.contains("allow_remote_vpc_dns_resolution = true"));
+        assert!(hcl.contains(r#"resource "aws_vpc_peering_connection_accepter" "eks_data""#));
+        assert!(hcl.contains("vpc_peering_connection_id = aws_vpc_peering_connection.eks_data.id"));
+        assert!(hcl.contains("auto_accept = true"));
+        assert!(hcl.contains("provider = aws.west"));
+
+        let auto_accepted = VpcPeeringConnection {
+            auto_accept: Some(true.into()),
+            ..peering
+        };
+        assert!(matches!(
+            Block::try_from(auto_accepted),
+            Err(Error::ConflictingArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_peering_rejects_overlapping_cidr_blocks() {
+        let eks = vpc("eks", 0);
+        let data = vpc("data", 0);
+
+        let peering = VpcPeeringConnection {
+            name: "eks_data".to_string(),
+            vpc: &eks,
+            peer_vpc: &data,
+            peer_owner_id: None,
+            peer_region: None,
+            auto_accept: Some(true.into()),
+            accepter: None,
+            requester: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        assert!(matches!(
+            Block::try_from(peering),
+            Err(Error::InvalidArgument {
+                argument: "peer_vpc_id",
+                ..
+            })
+        ));
+    }
+}
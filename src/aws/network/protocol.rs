@@ -0,0 +1,157 @@
+use crate::error::Error;
+use hcl::structure::BlockBuilder;
+use hcl::Expression;
+
+/// An inclusive range of TCP or UDP ports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortRange {
+    from: u16,
+    to: u16,
+}
+
+impl PortRange {
+    /// Every port, 0 to 65535.
+    pub const ALL: PortRange = PortRange { from: 0, to: 65535 };
+
+    /// The ports `from` through `to`.
+    pub fn new(from: u16, to: u16) -> Result<Self, Error> {
+        if from > to {
+            return Err(Error::invalid(
+                "port_range",
+                "from_port",
+                format!("from port {} is greater than to port {}", from, to),
+            ));
+        }
+
+        Ok(PortRange { from, to })
+    }
+
+    /// A single port, such as 443.
+    pub fn single(port: u16) -> Self {
+        PortRange {
+            from: port,
+            to: port,
+        }
+    }
+
+    /// First port of the range.
+    pub fn from(&self) -> u16 {
+        self.from
+    }
+
+    /// Last port of the range.
+    pub fn to(&self) -> u16 {
+        self.to
+    }
+}
+
+/// ICMP type and code a rule matches. `None` matches every type or code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IcmpFilter {
+    /// ICMP type, such as 8 for echo requests.
+    pub icmp_type: Option<u8>,
+    /// ICMP code within the type.
+    pub code: Option<u8>,
+}
+
+/// Traffic a rule matches: an IP protocol and, for TCP, UDP and ICMP, its ports or types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Every protocol and port.
+    All,
+    /// TCP on a range of ports.
+    Tcp(PortRange),
+    /// UDP on a range of ports.
+    Udp(PortRange),
+    /// ICMP for IPv4.
+    Icmp(IcmpFilter),
+    /// ICMP for IPv6.
+    Icmpv6(IcmpFilter),
+    /// Any other protocol by IANA number, such as 50 for ESP. Matches every port.
+    Number(u8),
+}
+
+impl Protocol {
+    /// Checks that ICMP codes come with a type and that TCP, UDP and ICMP are not given by
+    /// number, since they need ports or types. Errors are reported against `argument`.
+    pub fn validate(&self, resource: &'static str, argument: &'static str) -> Result<(), Error> {
+        match self {
+            Protocol::Icmp(filter) | Protocol::Icmpv6(filter)
+                if filter.code.is_some() && filter.icmp_type.is_none() =>
+            {
+                Err(Error::invalid(
+                    resource,
+                    argument,
+                    "an ICMP code requires an ICMP type",
+                ))
+            }
+            Protocol::Number(number @ (1 | 6 | 17 | 58)) => Err(Error::invalid(
+                resource,
+                argument,
+                format!(
+                    "protocol {} needs a port range or ICMP type, use its named variant",
+                    number
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds `ip_protocol`, `from_port` and `to_port` to a security group rule block. ICMP
+    /// types and codes are given as the ports.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        let (protocol, ports) = match self {
+            Protocol::All => ("-1".to_string(), None),
+            Protocol::Tcp(range) => ("tcp".to_string(), Some(Self::ports(range))),
+            Protocol::Udp(range) => ("udp".to_string(), Some(Self::ports(range))),
+            Protocol::Icmp(filter) => ("icmp".to_string(), Some(Self::icmp(filter))),
+            Protocol::Icmpv6(filter) => ("icmpv6".to_string(), Some(Self::icmp(filter))),
+            Protocol::Number(number) => (number.to_string(), None),
+        };
+
+        let block = block.add_attribute(("ip_protocol", protocol));
+        match ports {
+            Some((from, to)) => block
+                .add_attribute(("from_port", Expression::from(from)))
+                .add_attribute(("to_port", Expression::from(to))),
+            None => block,
+        }
+    }
+
+    /// Adds `protocol` and either `from_port` and `to_port` or `icmp_type` and `icmp_code`
+    /// to a network ACL rule block.
+    pub fn apply_to_acl_rule(self, block: BlockBuilder) -> BlockBuilder {
+        let (protocol, ports, icmp) = match self {
+            Protocol::All => ("-1".to_string(), None, None),
+            Protocol::Tcp(range) => ("tcp".to_string(), Some(Self::ports(range)), None),
+            Protocol::Udp(range) => ("udp".to_string(), Some(Self::ports(range)), None),
+            Protocol::Icmp(filter) => ("icmp".to_string(), None, Some(Self::icmp(filter))),
+            Protocol::Icmpv6(filter) => ("58".to_string(), None, Some(Self::icmp(filter))),
+            Protocol::Number(number) => (number.to_string(), None, None),
+        };
+
+        let mut block = block.add_attribute(("protocol", protocol));
+        if let Some((from, to)) = ports {
+            block = block
+                .add_attribute(("from_port", Expression::from(from)))
+                .add_attribute(("to_port", Expression::from(to)));
+        }
+        if let Some((icmp_type, icmp_code)) = icmp {
+            block = block
+                .add_attribute(("icmp_type", Expression::from(icmp_type)))
+                .add_attribute(("icmp_code", Expression::from(icmp_code)));
+        }
+        block
+    }
+
+    fn ports(range: PortRange) -> (i64, i64) {
+        (range.from.into(), range.to.into())
+    }
+
+    fn icmp(filter: IcmpFilter) -> (i64, i64) {
+        (
+            filter.icmp_type.map_or(-1, i64::from),
+            filter.code.map_or(-1, i64::from),
+        )
+    }
+}
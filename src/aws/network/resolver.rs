@@ -0,0 +1,688 @@
+use crate::aws::network::security_group::SecurityGroup;
+use crate::aws::network::subnet::Subnet;
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::{Block, Expression};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::ops::RangeInclusive;
+
+/// Number of addresses a resolver endpoint accepts.
+const ENDPOINT_ADDRESSES: RangeInclusive<usize> = 2..=6;
+
+/// Whether the Route 53 Resolver answers reverse lookups for the VPC's own addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutodefinedReverse {
+    Enable,
+    Disable,
+    /// Follows the account-level setting.
+    UseLocalResourceSetting,
+}
+
+impl fmt::Display for AutodefinedReverse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AutodefinedReverse::Enable => write!(f, "ENABLE"),
+            AutodefinedReverse::Disable => write!(f, "DISABLE"),
+            AutodefinedReverse::UseLocalResourceSetting => write!(f, "USE_LOCAL_RESOURCE_SETTING"),
+        }
+    }
+}
+
+impl IntoExpression for AutodefinedReverse {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(AutodefinedReverse);
+
+/// Represents the Route 53 Resolver settings of a VPC.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route53_resolver_config")]
+pub struct ResolverConfig<'a> {
+    /// The name of the resolver config.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC the settings apply to.
+    #[hcl(reference = "id", rename = "resource_id")]
+    pub vpc: &'a Vpc,
+
+    /// Whether reverse lookups of the VPC's addresses are answered automatically.
+    pub autodefined_reverse_flag: Value<AutodefinedReverse>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl ResolverConfig<'_> {
+    /// Checks the settings against the constraints enforced by AWS, including that the
+    /// VPC has DNS support for the resolver to answer in.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        require_dns_support(Self::TYPE, self.vpc)
+    }
+
+    /// The ID of the resolver config.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ID of the AWS account that owns the resolver config.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+}
+
+/// Represents DNSSEC validation by the Route 53 Resolver in a VPC.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route53_resolver_dnssec_config")]
+pub struct ResolverDnssecConfig<'a> {
+    /// The name of the DNSSEC config.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC to validate responses in.
+    #[hcl(reference = "id", rename = "resource_id")]
+    pub vpc: &'a Vpc,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl ResolverDnssecConfig<'_> {
+    /// Checks the DNSSEC config against the constraints enforced by AWS, including that the
+    /// VPC has DNS support for the resolver to answer in.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        require_dns_support(Self::TYPE, self.vpc)
+    }
+
+    /// The ID of the DNSSEC config.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The validation status, such as `ENABLED`.
+    pub fn validation_status(&self) -> Reference {
+        self.attr("validation_status")
+    }
+}
+
+/// Whether a resolver endpoint answers queries from outside the VPC or forwards queries
+/// out of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointDirection {
+    /// Lets resolvers outside the VPC, such as on-premises ones, query the VPC's names.
+    Inbound,
+    /// Lets resolver rules forward the VPC's queries to resolvers outside it.
+    Outbound,
+}
+
+impl fmt::Display for EndpointDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EndpointDirection::Inbound => write!(f, "INBOUND"),
+            EndpointDirection::Outbound => write!(f, "OUTBOUND"),
+        }
+    }
+}
+
+impl IntoExpression for EndpointDirection {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(EndpointDirection);
+
+/// An address a resolver endpoint listens or sends on, rendered as an `ip_address` block.
+#[derive(Debug, Clone)]
+pub struct EndpointAddress<'a> {
+    /// The subnet to take the address from.
+    pub subnet: &'a Subnet<'a>,
+    /// A fixed address in the subnet, instead of one AWS picks.
+    pub ip: Option<IpAddr>,
+}
+
+impl<'a> From<&'a Subnet<'a>> for EndpointAddress<'a> {
+    fn from(subnet: &'a Subnet<'a>) -> Self {
+        EndpointAddress { subnet, ip: None }
+    }
+}
+
+impl From<EndpointAddress<'_>> for Block {
+    fn from(address: EndpointAddress<'_>) -> Self {
+        let block = Block::builder("ip_address").add_attribute(("subnet_id", address.subnet.id()));
+        match address.ip {
+            Some(ip) => block.add_attribute(("ip", ip.into_expression())).build(),
+            None => block.build(),
+        }
+    }
+}
+
+/// Represents a Route 53 Resolver endpoint, the network interfaces through which DNS
+/// queries enter or leave a VPC.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route53_resolver_endpoint")]
+pub struct ResolverEndpoint<'a> {
+    /// The name of the endpoint. The block label is the name sanitized into an identifier,
+    /// so names with spaces or `.` are allowed.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+
+    /// Whether the endpoint is inbound or outbound.
+    pub direction: Value<EndpointDirection>,
+
+    /// Security groups of the endpoint's network interfaces. They must allow DNS traffic
+    /// to or from the resolvers on the other side.
+    #[hcl(reference = "id", rename = "security_group_ids")]
+    pub security_groups: Vec<&'a SecurityGroup<'a>>,
+
+    /// The addresses of the endpoint, at least two and ideally in different zones.
+    #[hcl(block)]
+    pub ip_addresses: Vec<EndpointAddress<'a>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl ResolverEndpoint<'_> {
+    /// Checks the endpoint against the constraints enforced by AWS, including that its
+    /// VPC has DNS support.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.security_groups.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "security_group_ids",
+            });
+        }
+
+        if !ENDPOINT_ADDRESSES.contains(&self.ip_addresses.len()) {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "ip_address",
+                format!(
+                    "must have between 2 and 6 addresses, got {}",
+                    self.ip_addresses.len()
+                ),
+            ));
+        }
+
+        let vpc = self.ip_addresses[0].subnet.vpc;
+        if let Some(other) = self
+            .ip_addresses
+            .iter()
+            .find(|address| address.subnet.vpc.name != vpc.name)
+        {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "ip_address",
+                format!(
+                    "subnet `{}` is not in `{}` like the endpoint's other subnets",
+                    other.subnet.name, vpc.name
+                ),
+            ));
+        }
+
+        require_dns_support(Self::TYPE, vpc)
+    }
+
+    /// The ID of the endpoint.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the endpoint.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the VPC the endpoint is in.
+    pub fn host_vpc_id(&self) -> Reference {
+        self.attr("host_vpc_id")
+    }
+}
+
+/// What a resolver rule does with queries for its domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleType {
+    /// Forwards queries to the rule's target addresses through an outbound endpoint.
+    Forward,
+    /// Resolves queries with the Route 53 Resolver, overriding a broader forward rule.
+    System,
+    /// Resolves queries recursively. Only used by the rule AWS creates for the internet.
+    Recursive,
+}
+
+impl fmt::Display for RuleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleType::Forward => write!(f, "FORWARD"),
+            RuleType::System => write!(f, "SYSTEM"),
+            RuleType::Recursive => write!(f, "RECURSIVE"),
+        }
+    }
+}
+
+impl IntoExpression for RuleType {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(RuleType);
+
+/// A resolver that a forward rule sends queries to, rendered as a `target_ip` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForwardTarget {
+    pub ip: IpAddr,
+    /// The port the resolver listens on, 53 when `None`.
+    pub port: Option<u16>,
+}
+
+impl From<IpAddr> for ForwardTarget {
+    fn from(ip: IpAddr) -> Self {
+        ForwardTarget { ip, port: None }
+    }
+}
+
+impl From<Ipv4Addr> for ForwardTarget {
+    fn from(ip: Ipv4Addr) -> Self {
+        IpAddr::from(ip).into()
+    }
+}
+
+impl From<ForwardTarget> for Block {
+    fn from(target: ForwardTarget) -> Self {
+        let block = Block::builder("target_ip").add_attribute(("ip", target.ip.into_expression()));
+        match target.port {
+            Some(port) => block.add_attribute(("port", port)).build(),
+            None => block.build(),
+        }
+    }
+}
+
+/// Represents a Route 53 Resolver rule, which decides how queries for a domain are
+/// resolved in the VPCs it is associated with through [`ResolverRule::associate`].
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route53_resolver_rule")]
+pub struct ResolverRule<'a> {
+    /// The name of the rule, such as the domain it forwards. The block label is the name
+    /// sanitized into an identifier, e.g. `corp_example_com` for `corp.example.com`.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+
+    /// The domain the rule applies to, including its subdomains, such as `corp.example.com`.
+    pub domain_name: Value<String>,
+
+    /// What the rule does with matching queries.
+    pub rule_type: Value<RuleType>,
+
+    /// The outbound endpoint forward rules send queries through.
+    #[hcl(reference = "id", rename = "resolver_endpoint_id")]
+    pub resolver_endpoint: Option<&'a ResolverEndpoint<'a>>,
+
+    /// The resolvers forward rules send queries to.
+    #[hcl(block = "target_ip")]
+    pub target_ips: Vec<ForwardTarget>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl<'a> ResolverRule<'a> {
+    /// Forwards queries for `domain_name` through `endpoint` to the resolvers at `targets`,
+    /// such as on-premises DNS servers.
+    pub fn forward<I>(
+        name: &str,
+        domain_name: &str,
+        endpoint: &'a ResolverEndpoint<'a>,
+        targets: I,
+    ) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ForwardTarget>,
+    {
+        ResolverRule {
+            name: name.to_string(),
+            domain_name: domain_name.into(),
+            rule_type: RuleType::Forward.into(),
+            resolver_endpoint: Some(endpoint),
+            target_ips: targets.into_iter().map(Into::into).collect(),
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    /// Checks the rule against the constraints enforced by AWS: forward rules need an
+    /// outbound endpoint and at least one target, other rules take neither.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.domain_name.as_literal().is_some_and(String::is_empty) {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "domain_name",
+            });
+        }
+
+        match self.rule_type.as_literal() {
+            Some(RuleType::Forward) => {
+                let Some(endpoint) = self.resolver_endpoint else {
+                    return Err(Error::MissingArgument {
+                        resource: Self::TYPE,
+                        argument: "resolver_endpoint_id",
+                    });
+                };
+                if endpoint.direction.as_literal() == Some(&EndpointDirection::Inbound) {
+                    return Err(Error::invalid(
+                        Self::TYPE,
+                        "resolver_endpoint_id",
+                        format!(
+                            "`{}` is an inbound endpoint, forward rules need an outbound one",
+                            endpoint.name
+                        ),
+                    ));
+                }
+                if self.target_ips.is_empty() {
+                    return Err(Error::MissingArgument {
+                        resource: Self::TYPE,
+                        argument: "target_ip",
+                    });
+                }
+            }
+            Some(RuleType::System | RuleType::Recursive) => {
+                if self.resolver_endpoint.is_some() {
+                    return Err(Error::ConflictingArguments {
+                        resource: Self::TYPE,
+                        first: "rule_type",
+                        second: "resolver_endpoint_id",
+                    });
+                }
+                if !self.target_ips.is_empty() {
+                    return Err(Error::ConflictingArguments {
+                        resource: Self::TYPE,
+                        first: "rule_type",
+                        second: "target_ip",
+                    });
+                }
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Applies the rule to queries from `vpc`.
+    pub fn associate(&'a self, name: &str, vpc: &'a Vpc) -> ResolverRuleAssociation<'a> {
+        ResolverRuleAssociation {
+            name: name.to_string(),
+            resolver_rule: self,
+            vpc,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    /// The ID of the rule.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the rule.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+/// Represents the association of a Route 53 Resolver rule with a VPC.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route53_resolver_rule_association")]
+pub struct ResolverRuleAssociation<'a> {
+    /// The name of the association.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The rule to apply.
+    #[hcl(reference = "id")]
+    pub resolver_rule: &'a ResolverRule<'a>,
+
+    /// The VPC whose queries the rule applies to.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl ResolverRuleAssociation<'_> {
+    /// Checks the association against the constraints enforced by AWS, including that the
+    /// VPC has DNS support for the rule to apply in.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        require_dns_support(Self::TYPE, self.vpc)
+    }
+
+    /// The ID of the association.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Resolver settings have no effect in a VPC whose `enable_dns_support` is off.
+fn require_dns_support(resource: &'static str, vpc: &Vpc) -> Result<(), Error> {
+    if vpc.enable_dns_support.as_ref().and_then(Value::as_literal) == Some(&false) {
+        return Err(Error::invalid(
+            resource,
+            "resource_id",
+            format!(
+                "`{}` disables `enable_dns_support`, so the resolver does not answer in it",
+                vpc.name
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::Stack;
+
+    #[test]
+    fn test_resolver_configs_to_hcl() {
+        let vpc = Vpc {
+            enable_dns_hostnames: Some(true.into()),
+            enable_dns_support: Some(true.into()),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
+        };
+        let config = ResolverConfig {
+            name: "main".to_string(),
+            vpc: &vpc,
+            autodefined_reverse_flag: AutodefinedReverse::Disable.into(),
+            meta: MetaArguments::default(),
+        };
+
+        let mut stack = Stack::new();
+        stack
+            .add(config.clone())
+            .unwrap()
+            .add(ResolverDnssecConfig {
+                name: "main".to_string(),
+                vpc: &vpc,
+                meta: MetaArguments::default(),
+            })
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_config" "main""#));
+        assert!(hcl.contains("resource_id = aws_vpc.main.id"));
+        assert!(hcl.contains(r#"autodefined_reverse_flag = "DISABLE""#));
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_dnssec_config" "main""#));
+
+        let no_dns = Vpc {
+            enable_dns_hostnames: None,
+            enable_dns_support: Some(false.into()),
+            ..vpc.clone()
+        };
+        assert!(matches!(
+            Block::try_from(ResolverConfig {
+                vpc: &no_dns,
+                ..config
+            }),
+            Err(Error::InvalidArgument {
+                argument: "resource_id",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_forward_rule_to_hcl() {
+        let vpc = Vpc {
+            enable_dns_hostnames: Some(true.into()),
+            enable_dns_support: Some(true.into()),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
+        };
+        let a = Subnet::new("a", &vpc, "10.0.1.0/24".parse().unwrap());
+        let b = Subnet::new("b", &vpc, "10.0.2.0/24".parse().unwrap());
+        let dns = SecurityGroup {
+            name: "dns".to_string(),
+            description: None,
+            vpc: &vpc,
+            revoke_rules_on_delete: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let outbound = ResolverEndpoint {
+            name: "outbound".to_string(),
+            direction: EndpointDirection::Outbound.into(),
+            security_groups: vec![&dns],
+            ip_addresses: vec![
+                EndpointAddress {
+                    subnet: &a,
+                    ip: Some(Ipv4Addr::new(10, 0, 1, 10).into()),
+                },
+                (&b).into(),
+            ],
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let on_prem = ForwardTarget {
+            ip: Ipv4Addr::new(192, 168, 0, 2).into(),
+            port: Some(5353),
+        };
+        let rule = ResolverRule::forward(
+            "corp",
+            "corp.example.com",
+            &outbound,
+            [on_prem, Ipv4Addr::new(192, 168, 0, 3).into()],
+        );
+
+        let mut stack = Stack::new();
+        stack
+            .add(outbound.clone())
+            .unwrap()
+            .add(rule.clone())
+            .unwrap()
+            .add(rule.associate("corp_main", &vpc))
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_endpoint" "outbound""#));
+        assert!(hcl.contains(r#"direction = "OUTBOUND""#));
+        assert!(hcl.contains("aws_security_group.dns.id"));
+        assert!(hcl.contains(
+            "  ip_address {\n    subnet_id = aws_subnet.a.id\n    ip = \"10.0.1.10\"\n  }"
+        ));
+        assert!(hcl.contains("  ip_address {\n    subnet_id = aws_subnet.b.id\n  }"));
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_rule" "corp""#));
+        assert!(hcl.contains(r#"rule_type = "FORWARD""#));
+        assert!(hcl.contains("resolver_endpoint_id = aws_route53_resolver_endpoint.outbound.id"));
+        assert!(hcl.contains("  target_ip {\n    ip = \"192.168.0.2\"\n    port = 5353\n  }"));
+        assert!(hcl.contains("  target_ip {\n    ip = \"192.168.0.3\"\n  }"));
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_rule_association" "corp_main""#));
+        assert!(hcl.contains("resolver_rule_id = aws_route53_resolver_rule.corp.id"));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+
+        let dotted =
+            ResolverRule::forward("corp.example.com", "corp.example.com", &outbound, [on_prem]);
+        let hcl = hcl::to_string(&Block::try_from(dotted.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_route53_resolver_rule" "corp_example_com""#));
+        assert!(hcl.contains(r#"name = "corp.example.com""#));
+        let hcl =
+            hcl::to_string(&Block::try_from(dotted.associate("corp_main", &vpc)).unwrap()).unwrap();
+        assert!(hcl.contains("resolver_rule_id = aws_route53_resolver_rule.corp_example_com.id"));
+
+        assert!(matches!(
+            Block::try_from(ResolverEndpoint {
+                ip_addresses: vec![(&a).into()],
+                ..outbound.clone()
+            }),
+            Err(Error::InvalidArgument {
+                argument: "ip_address",
+                ..
+            })
+        ));
+
+        let inbound = ResolverEndpoint {
+            name: "inbound".to_string(),
+            direction: EndpointDirection::Inbound.into(),
+            ..outbound.clone()
+        };
+        assert!(matches!(
+            Block::try_from(ResolverRule::forward(
+                "corp",
+                "corp.example.com",
+                &inbound,
+                [on_prem]
+            )),
+            Err(Error::InvalidArgument {
+                argument: "resolver_endpoint_id",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Block::try_from(ResolverRule {
+                target_ips: Vec::new(),
+                ..rule.clone()
+            }),
+            Err(Error::MissingArgument {
+                argument: "target_ip",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Block::try_from(ResolverRule {
+                rule_type: RuleType::System.into(),
+                target_ips: Vec::new(),
+                ..rule
+            }),
+            Err(Error::ConflictingArguments {
+                second: "resolver_endpoint_id",
+                ..
+            })
+        ));
+    }
+}
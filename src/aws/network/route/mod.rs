@@ -1 +1,344 @@
-pub mod table;
\ No newline at end of file
+pub mod table;
+
+pub use table::{AssociationTarget, RouteTable, RouteTableAssociation, RouteTableDataSource};
+
+use crate::aws::network::cidr;
+use crate::aws::network::gateway::{EgressOnlyInternet, Internet, NAT};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use hcl::structure::BlockBuilder;
+
+/// Traffic a route applies to.
+#[derive(Debug, Clone)]
+pub enum RouteDestination {
+    /// An IPv4 CIDR block, such as `0.0.0.0/0`.
+    Ipv4(Value<cidr::Block>),
+    /// An IPv6 CIDR block, such as `::/0`.
+    Ipv6(Value<cidr::Ipv6Block>),
+    /// A managed prefix list ID.
+    PrefixList(Value<String>),
+}
+
+impl RouteDestination {
+    /// Adds the matching `destination_*` argument to a route block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            RouteDestination::Ipv4(cidr) => block.add_attribute(("destination_cidr_block", cidr)),
+            RouteDestination::Ipv6(cidr) => {
+                block.add_attribute(("destination_ipv6_cidr_block", cidr))
+            }
+            RouteDestination::PrefixList(id) => {
+                block.add_attribute(("destination_prefix_list_id", id))
+            }
+        }
+    }
+}
+
+/// Where a route sends matching traffic. Each variant holds the target's ID and converts
+/// from the matching resource, e.g. `RouteTarget::from(&nat)`.
+#[derive(Debug, Clone)]
+pub enum RouteTarget {
+    /// An internet gateway.
+    InternetGateway(Value<String>),
+    /// An egress-only internet gateway, for outbound IPv6 traffic.
+    EgressOnlyInternetGateway(Value<String>),
+    /// A NAT gateway.
+    NatGateway(Value<String>),
+    /// A Gateway Load Balancer VPC endpoint. Converts with `RouteTarget::try_from(&endpoint)`,
+    /// which rejects other kinds of endpoint.
+    VpcEndpoint(Value<String>),
+    /// A VPC peering connection.
+    VpcPeeringConnection(Value<String>),
+    /// A transit gateway.
+    TransitGateway(Value<String>),
+    /// An elastic network interface.
+    NetworkInterface(Value<String>),
+}
+
+impl RouteTarget {
+    /// Name of the route argument holding the target's ID.
+    pub fn argument(&self) -> &'static str {
+        match self {
+            RouteTarget::InternetGateway(_) => "gateway_id",
+            RouteTarget::EgressOnlyInternetGateway(_) => "egress_only_gateway_id",
+            RouteTarget::NatGateway(_) => "nat_gateway_id",
+            RouteTarget::VpcEndpoint(_) => "vpc_endpoint_id",
+            RouteTarget::VpcPeeringConnection(_) => "vpc_peering_connection_id",
+            RouteTarget::TransitGateway(_) => "transit_gateway_id",
+            RouteTarget::NetworkInterface(_) => "network_interface_id",
+        }
+    }
+
+    /// Adds the target's ID argument to a route block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        let argument = self.argument();
+        match self {
+            RouteTarget::InternetGateway(id)
+            | RouteTarget::EgressOnlyInternetGateway(id)
+            | RouteTarget::NatGateway(id)
+            | RouteTarget::VpcEndpoint(id)
+            | RouteTarget::VpcPeeringConnection(id)
+            | RouteTarget::TransitGateway(id)
+            | RouteTarget::NetworkInterface(id) => block.add_attribute((argument, id)),
+        }
+    }
+}
+
+impl From<&Internet<'_>> for RouteTarget {
+    fn from(gateway: &Internet<'_>) -> Self {
+        RouteTarget::InternetGateway(gateway.id().into())
+    }
+}
+
+impl From<&EgressOnlyInternet<'_>> for RouteTarget {
+    fn from(gateway: &EgressOnlyInternet<'_>) -> Self {
+        RouteTarget::EgressOnlyInternetGateway(gateway.id().into())
+    }
+}
+
+impl From<&NAT<'_>> for RouteTarget {
+    fn from(gateway: &NAT<'_>) -> Self {
+        RouteTarget::NatGateway(gateway.id().into())
+    }
+}
+
+/// Represents a route in an AWS Route Table.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route")]
+pub struct Route<'a> {
+    /// The name of the route.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The route table the route is added to.
+    #[hcl(reference = "id")]
+    pub route_table: &'a RouteTable<'a>,
+
+    /// The traffic the route applies to.
+    #[hcl(flatten)]
+    pub destination: RouteDestination,
+
+    /// Where matching traffic is sent.
+    #[hcl(flatten)]
+    pub target: RouteTarget,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl Route<'_> {
+    /// Checks the route arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if matches!(self.target, RouteTarget::EgressOnlyInternetGateway(_))
+            && !matches!(self.destination, RouteDestination::Ipv6(_))
+        {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "egress_only_gateway_id",
+                "egress-only internet gateways only route IPv6 destinations",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the route, made of the route table ID and destination.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The origin of the route, such as `CreateRoute` or `EnableVgwRoutePropagation`.
+    pub fn origin(&self) -> Reference {
+        self.attr("origin")
+    }
+
+    /// The state of the route, `active` or `blackhole`.
+    pub fn state(&self) -> Reference {
+        self.attr("state")
+    }
+}
+
+/// Represents a data source for a route in an AWS Route Table.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_route")]
+pub struct RouteDataSource {
+    /// The name of the route data source.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The ID of the route table containing the route.
+    pub route_table_id: String,
+
+    /// The IPv4 CIDR block of the route.
+    pub destination_cidr_block: Option<String>,
+
+    /// The IPv6 CIDR block of the route.
+    pub destination_ipv6_cidr_block: Option<String>,
+
+    /// The managed prefix list ID of the route.
+    pub destination_prefix_list_id: Option<String>,
+
+    /// The internet or virtual private gateway ID of the route.
+    pub gateway_id: Option<String>,
+
+    /// The NAT gateway ID of the route.
+    pub nat_gateway_id: Option<String>,
+
+    /// The VPC endpoint ID of the route.
+    pub vpc_endpoint_id: Option<String>,
+
+    /// The VPC peering connection ID of the route.
+    pub vpc_peering_connection_id: Option<String>,
+
+    /// The transit gateway ID of the route.
+    pub transit_gateway_id: Option<String>,
+
+    /// The network interface ID of the route.
+    pub network_interface_id: Option<String>,
+}
+
+impl RouteDataSource {
+    /// The ID of the route.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The NAT gateway ID of the route.
+    pub fn nat_gateway_id(&self) -> Reference {
+        self.attr("nat_gateway_id")
+    }
+
+    /// The internet or virtual private gateway ID of the route.
+    pub fn gateway_id(&self) -> Reference {
+        self.attr("gateway_id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::subnet::Subnet;
+    use crate::aws::network::vpc::{ElasticIp, Vpc};
+    use crate::stack::Stack;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_routes_to_gateways() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+
+        let public_subnet = Subnet {
+            map_public_ip_on_launch: Some(true.into()),
+            ..Subnet::new("public", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+
+        let internet = Internet {
+            name: "main".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+
+        let elastic_ip = ElasticIp::vpc("nat");
+
+        let nat = NAT {
+            id: Some("main".to_string()),
+            vpc: &vpc,
+            subnet: &public_subnet,
+            elastic_ip: Some(&elastic_ip),
+            connectivity_type: None,
+            tags: None,
+            state: None,
+            meta: MetaArguments::default(),
+        };
+
+        let public = RouteTable {
+            name: "public".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let private = RouteTable {
+            name: "private".to_string(),
+            ..public.clone()
+        };
+
+        let default_route = cidr::Block::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+
+        let mut stack = Stack::new();
+        stack
+            .add(Route {
+                name: "public_internet".to_string(),
+                route_table: &public,
+                destination: RouteDestination::Ipv4(default_route.clone().into()),
+                target: (&internet).into(),
+                meta: MetaArguments::default(),
+            })
+            .unwrap()
+            .add(Route {
+                name: "private_nat".to_string(),
+                route_table: &private,
+                destination: RouteDestination::Ipv4(default_route.into()),
+                target: (&nat).into(),
+                meta: MetaArguments::default(),
+            })
+            .unwrap()
+            .add(Route {
+                name: "private_ipv6".to_string(),
+                route_table: &private,
+                destination: RouteDestination::Ipv6(
+                    cidr::Ipv6Block::new(Ipv6Addr::UNSPECIFIED, 0)
+                        .unwrap()
+                        .into(),
+                ),
+                target: RouteTarget::NetworkInterface("eni-12345".into()),
+                meta: MetaArguments::default(),
+            })
+            .unwrap();
+
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_route" "public_internet""#));
+        assert!(hcl.contains("route_table_id = aws_route_table.public.id"));
+        assert!(hcl.contains(r#"destination_cidr_block = "0.0.0.0/0""#));
+        assert!(hcl.contains("gateway_id = aws_internet_gateway.main.id"));
+        assert!(hcl.contains("nat_gateway_id = aws_nat_gateway.main.id"));
+        assert!(hcl.contains(r#"destination_ipv6_cidr_block = "::/0""#));
+        assert!(hcl.contains(r#"network_interface_id = "eni-12345""#));
+    }
+
+    #[test]
+    fn test_route_data_source_to_hcl() {
+        let data_source = RouteDataSource {
+            name: "default".to_string(),
+            route_table_id: "rtb-12345".to_string(),
+            destination_cidr_block: Some("0.0.0.0/0".to_string()),
+            destination_ipv6_cidr_block: None,
+            destination_prefix_list_id: None,
+            gateway_id: None,
+            nat_gateway_id: None,
+            vpc_endpoint_id: None,
+            vpc_peering_connection_id: None,
+            transit_gateway_id: None,
+            network_interface_id: None,
+        };
+
+        let hcl = hcl::to_string(&hcl::Block::from(data_source)).unwrap();
+        assert!(hcl.contains(r#"data "aws_route" "default""#));
+        assert!(hcl.contains(r#"route_table_id = "rtb-12345""#));
+        assert!(hcl.contains(r#"destination_cidr_block = "0.0.0.0/0""#));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::aws::network::gateway::Internet;
+use crate::aws::network::subnet::Subnet;
+pub use crate::aws::network::vpc::Filter;
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use hcl::structure::BlockBuilder;
+use std::collections::HashMap;
+
+/// Represents an AWS Route Table resource. Routes are added with separate
+/// [`Route`](super::Route) resources.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route_table")]
+pub struct RouteTable<'a> {
+    /// The name of the route table.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The VPC the route table belongs to.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// Virtual private gateway IDs whose routes are propagated into the table.
+    pub propagating_vgws: Option<Value<Vec<String>>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl RouteTable<'_> {
+    /// Checks the route table arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the route table.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the route table.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the AWS account that owns the route table.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+}
+
+/// What a route table is associated with.
+#[derive(Debug, Clone)]
+pub enum AssociationTarget {
+    /// A subnet, whose traffic is routed by the table.
+    Subnet(Value<String>),
+    /// An internet or virtual private gateway, for ingress routing.
+    Gateway(Value<String>),
+}
+
+impl AssociationTarget {
+    /// Adds the `subnet_id` or `gateway_id` argument to an association block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            AssociationTarget::Subnet(id) => block.add_attribute(("subnet_id", id)),
+            AssociationTarget::Gateway(id) => block.add_attribute(("gateway_id", id)),
+        }
+    }
+}
+
+impl From<&Subnet<'_>> for AssociationTarget {
+    fn from(subnet: &Subnet<'_>) -> Self {
+        AssociationTarget::Subnet(subnet.id().into())
+    }
+}
+
+impl From<&Internet<'_>> for AssociationTarget {
+    fn from(gateway: &Internet<'_>) -> Self {
+        AssociationTarget::Gateway(gateway.id().into())
+    }
+}
+
+/// Represents an AWS Route Table Association resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_route_table_association")]
+pub struct RouteTableAssociation<'a> {
+    /// The name of the association.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The subnet or gateway to associate with the route table.
+    #[hcl(flatten)]
+    pub target: AssociationTarget,
+
+    /// The route table to associate.
+    #[hcl(reference = "id")]
+    pub route_table: &'a RouteTable<'a>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl RouteTableAssociation<'_> {
+    /// Checks the association arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the association.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Represents a data source for an AWS Route Table.
+///
+/// The provider has no association data source; associations are read through
+/// [`RouteTableDataSource::associations`].
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_route_table")]
+pub struct RouteTableDataSource {
+    /// The name of the route table data source.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The ID of the specific route table to retrieve.
+    pub route_table_id: Option<String>,
+
+    /// The ID of a subnet associated with the route table.
+    pub subnet_id: Option<String>,
+
+    /// The ID of a gateway associated with the route table.
+    pub gateway_id: Option<String>,
+
+    /// The ID of the VPC the route table belongs to.
+    pub vpc_id: Option<String>,
+
+    /// One or more name-value pairs to filter by.
+    #[hcl(block)]
+    pub filter: Option<Vec<Filter>>,
+
+    /// A map of tags the route table must have.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+impl RouteTableDataSource {
+    /// The ID of the route table.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the route table.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The routes in the route table.
+    pub fn routes(&self) -> Reference {
+        self.attr("routes")
+    }
+
+    /// The subnet and gateway associations of the route table.
+    pub fn associations(&self) -> Reference {
+        self.attr("associations")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hcl::Block;
+
+    #[test]
+    fn test_route_table_and_association_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+
+        let subnet = Subnet::new("public", &vpc, "10.0.1.0/24".parse().unwrap());
+
+        let route_table = RouteTable {
+            name: "public".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: Some(HashMap::from([("Name".to_string(), "public".to_string())]).into()),
+            meta: MetaArguments::default(),
+        };
+
+        let association = RouteTableAssociation {
+            name: "public".to_string(),
+            target: (&subnet).into(),
+            route_table: &route_table,
+            meta: MetaArguments::default(),
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(route_table.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_route_table" "public""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+        assert!(hcl.contains(r#""Name" = "public""#));
+
+        let hcl = hcl::to_string(&Block::try_from(association).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_route_table_association" "public""#));
+        assert!(hcl.contains("subnet_id = aws_subnet.public.id"));
+        assert!(hcl.contains("route_table_id = aws_route_table.public.id"));
+    }
+
+    #[test]
+    fn test_route_table_data_source_to_hcl() {
+        let data_source = RouteTableDataSource {
+            name: "selected".to_string(),
+            route_table_id: None,
+            subnet_id: Some("subnet-12345".to_string()),
+            gateway_id: None,
+            vpc_id: None,
+            filter: None,
+            tags: None,
+        };
+        let routes = data_source.routes();
+
+        let hcl = hcl::to_string(&Block::from(data_source)).unwrap();
+        assert!(hcl.contains(r#"data "aws_route_table" "selected""#));
+        assert!(hcl.contains(r#"subnet_id = "subnet-12345""#));
+        assert_eq!(routes.to_string(), "data.aws_route_table.selected.routes");
+    }
+}
@@ -0,0 +1,396 @@
+use crate::aws::network::cidr;
+pub use crate::aws::network::protocol::{IcmpFilter, PortRange, Protocol};
+pub use crate::aws::network::vpc::Filter;
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use hcl::structure::BlockBuilder;
+use std::collections::HashMap;
+
+/// The other side of a rule: where ingress traffic comes from or egress traffic goes to.
+#[derive(Debug, Clone)]
+pub enum RulePeer {
+    /// An IPv4 CIDR block.
+    Cidr(Value<cidr::Block>),
+    /// An IPv6 CIDR block.
+    Ipv6Cidr(Value<cidr::Ipv6Block>),
+    /// Another security group, by ID.
+    SecurityGroup(Value<String>),
+    /// A managed prefix list, by ID.
+    PrefixList(Value<String>),
+}
+
+impl RulePeer {
+    /// Adds the matching `cidr_ipv4`, `cidr_ipv6`, `referenced_security_group_id` or
+    /// `prefix_list_id` argument to a rule block.
+    pub fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            RulePeer::Cidr(cidr) => block.add_attribute(("cidr_ipv4", cidr)),
+            RulePeer::Ipv6Cidr(cidr) => block.add_attribute(("cidr_ipv6", cidr)),
+            RulePeer::SecurityGroup(id) => {
+                block.add_attribute(("referenced_security_group_id", id))
+            }
+            RulePeer::PrefixList(id) => block.add_attribute(("prefix_list_id", id)),
+        }
+    }
+}
+
+impl From<cidr::Block> for RulePeer {
+    fn from(cidr: cidr::Block) -> Self {
+        RulePeer::Cidr(cidr.into())
+    }
+}
+
+impl From<cidr::Ipv6Block> for RulePeer {
+    fn from(cidr: cidr::Ipv6Block) -> Self {
+        RulePeer::Ipv6Cidr(cidr.into())
+    }
+}
+
+impl From<&SecurityGroup<'_>> for RulePeer {
+    fn from(group: &SecurityGroup<'_>) -> Self {
+        RulePeer::SecurityGroup(group.id().into())
+    }
+}
+
+/// Represents an AWS Security Group resource. Rules are added with separate
+/// [`SecurityGroupIngressRule`] and [`SecurityGroupEgressRule`] resources.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_security_group")]
+pub struct SecurityGroup<'a> {
+    /// The name of the security group. AWS also allows spaces and `.` in it, so the block
+    /// label is the name sanitized into an identifier, e.g. `web_servers` for `web servers`.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+
+    /// The description of the security group.
+    pub description: Option<Value<String>>,
+
+    /// The VPC the security group belongs to.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// Revokes every rule before deleting the group, to break cycles between groups that
+    /// reference each other.
+    pub revoke_rules_on_delete: Option<Value<bool>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl SecurityGroup<'_> {
+    /// Checks the security group arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if self.name.len() > 255 || self.name.starts_with("sg-") {
+            return Err(Error::invalid(
+                Self::TYPE,
+                "name",
+                "must be at most 255 characters and not start with `sg-`",
+            ));
+        }
+
+        if let Some(description) = self.description.as_ref().and_then(Value::as_literal) {
+            if description.len() > 255 {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "description",
+                    "must be at most 255 characters",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the security group.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the security group.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the AWS account that owns the security group.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+}
+
+/// Represents an inbound rule of an AWS Security Group.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_security_group_ingress_rule")]
+pub struct SecurityGroupIngressRule<'a> {
+    /// The name of the rule.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The security group the rule belongs to.
+    #[hcl(reference = "id")]
+    pub security_group: &'a SecurityGroup<'a>,
+
+    /// The protocol and ports the rule allows.
+    #[hcl(flatten)]
+    pub protocol: Protocol,
+
+    /// Where the allowed traffic comes from.
+    #[hcl(flatten)]
+    pub source: RulePeer,
+
+    /// The description of the rule.
+    pub description: Option<Value<String>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl SecurityGroupIngressRule<'_> {
+    /// Checks the rule arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        self.protocol.validate(Self::TYPE, "ip_protocol")
+    }
+
+    /// The ID of the security group rule.
+    pub fn security_group_rule_id(&self) -> Reference {
+        self.attr("security_group_rule_id")
+    }
+
+    /// The ARN of the security group rule.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+/// Represents an outbound rule of an AWS Security Group.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc_security_group_egress_rule")]
+pub struct SecurityGroupEgressRule<'a> {
+    /// The name of the rule.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The security group the rule belongs to.
+    #[hcl(reference = "id")]
+    pub security_group: &'a SecurityGroup<'a>,
+
+    /// The protocol and ports the rule allows.
+    #[hcl(flatten)]
+    pub protocol: Protocol,
+
+    /// Where the allowed traffic goes to.
+    #[hcl(flatten)]
+    pub destination: RulePeer,
+
+    /// The description of the rule.
+    pub description: Option<Value<String>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl SecurityGroupEgressRule<'_> {
+    /// Checks the rule arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        self.protocol.validate(Self::TYPE, "ip_protocol")
+    }
+
+    /// The ID of the security group rule.
+    pub fn security_group_rule_id(&self) -> Reference {
+        self.attr("security_group_rule_id")
+    }
+
+    /// The ARN of the security group rule.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+/// Represents a data source for an AWS Security Group.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_security_group")]
+pub struct SecurityGroupDataSource {
+    /// The name of the security group data source.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The ID of the specific security group to retrieve.
+    pub id: Option<String>,
+
+    /// The name of the security group to retrieve.
+    #[hcl(rename = "name")]
+    pub group_name: Option<String>,
+
+    /// The ID of the VPC the security group belongs to.
+    pub vpc_id: Option<String>,
+
+    /// One or more name-value pairs to filter by.
+    #[hcl(block)]
+    pub filter: Option<Vec<Filter>>,
+
+    /// A map of tags the security group must have.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+impl SecurityGroupDataSource {
+    /// The ID of the security group.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the security group.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hcl::Block;
+    use std::net::Ipv4Addr;
+
+    fn group<'a>(vpc: &'a Vpc, name: &str) -> SecurityGroup<'a> {
+        SecurityGroup {
+            name: name.to_string(),
+            description: Some("Managed by nexus7".into()),
+            vpc,
+            revoke_rules_on_delete: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    #[test]
+    fn test_security_group_and_rules_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let web = group(&vpc, "web");
+        let load_balancer = group(&vpc, "load_balancer");
+
+        let hcl = hcl::to_string(&Block::try_from(web.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_security_group" "web""#));
+        assert!(hcl.contains(r#"name = "web""#));
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+
+        let https = SecurityGroupIngressRule {
+            name: "web_https".to_string(),
+            security_group: &web,
+            protocol: Protocol::Tcp(PortRange::single(443)),
+            source: (&load_balancer).into(),
+            description: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let hcl = hcl::to_string(&Block::try_from(https).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_vpc_security_group_ingress_rule" "web_https""#));
+        assert!(hcl.contains("security_group_id = aws_security_group.web.id"));
+        assert!(hcl.contains(r#"ip_protocol = "tcp""#));
+        assert!(hcl.contains("from_port = 443"));
+        assert!(hcl.contains("to_port = 443"));
+        assert!(hcl.contains("referenced_security_group_id = aws_security_group.load_balancer.id"));
+
+        let egress = SecurityGroupEgressRule {
+            name: "web_all".to_string(),
+            security_group: &web,
+            protocol: Protocol::All,
+            destination: cidr::Block::new(Ipv4Addr::new(0, 0, 0, 0), 0)
+                .unwrap()
+                .into(),
+            description: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let hcl = hcl::to_string(&Block::try_from(egress).unwrap()).unwrap();
+        assert!(hcl.contains(r#"ip_protocol = "-1""#));
+        assert!(hcl.contains(r#"cidr_ipv4 = "0.0.0.0/0""#));
+        assert!(!hcl.contains("from_port"));
+    }
+
+    #[test]
+    fn test_security_group_name_is_not_an_identifier() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let web = group(&vpc, "web servers.v2");
+
+        let hcl = hcl::to_string(&Block::try_from(web.clone()).unwrap()).unwrap();
+        assert!(hcl.contains(r#"resource "aws_security_group" "web_servers_v2""#));
+        assert!(hcl.contains(r#"name = "web servers.v2""#));
+        assert_eq!(web.id().to_string(), "aws_security_group.web_servers_v2.id");
+    }
+
+    #[test]
+    fn test_icmp_rule_ports() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let web = group(&vpc, "web");
+
+        let ping = SecurityGroupIngressRule {
+            name: "ping".to_string(),
+            security_group: &web,
+            protocol: Protocol::Icmp(IcmpFilter {
+                icmp_type: Some(8),
+                code: None,
+            }),
+            source: RulePeer::PrefixList("pl-12345".into()),
+            description: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let hcl = hcl::to_string(&Block::try_from(ping.clone()).unwrap()).unwrap();
+        assert!(hcl.contains("from_port = 8"));
+        assert!(hcl.contains("to_port = -1"));
+        assert!(hcl.contains(r#"prefix_list_id = "pl-12345""#));
+
+        let code_only = SecurityGroupIngressRule {
+            protocol: Protocol::Icmp(IcmpFilter {
+                icmp_type: None,
+                code: Some(0),
+            }),
+            ..ping.clone()
+        };
+        assert!(Block::try_from(code_only).is_err());
+
+        let tcp_by_number = SecurityGroupIngressRule {
+            protocol: Protocol::Number(6),
+            ..ping
+        };
+        assert!(matches!(
+            Block::try_from(tcp_by_number),
+            Err(Error::InvalidArgument {
+                argument: "ip_protocol",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_security_group() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        assert!(Block::try_from(group(&vpc, "sg-web")).is_err());
+        assert!(PortRange::new(443, 80).is_err());
+    }
+}
@@ -1,79 +1,241 @@
-use crate::aws::availability_zone::AvailabilityZone;
+use crate::aws::availability_zone::{AvailabilityZone, AvailabilityZoneId};
 use crate::aws::network::{cidr, vpc::Vpc};
-use hcl::{Block, Expression, ObjectKey};
+use crate::aws::provider::AwsProvider;
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::output::Output;
+use crate::reference::{Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
 use std::collections::HashMap;
 
 /// Represents an AWS Subnet resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_subnet")]
 pub struct Subnet<'a> {
     /// The name of the subnet.
+    #[hcl(label)]
     pub name: String,
 
     /// The VPC in which the subnet is located.
+    #[hcl(reference = "id")]
     pub vpc: &'a Vpc,
 
     /// The IPv4 CIDR block for the subnet.
-    pub cidr_block: cidr::Block,
+    pub cidr_block: Value<cidr::Block>,
 
     /// The AZ for the subnet.
-    pub availability_zone: Option<AvailabilityZone>,
+    pub availability_zone: Option<Value<AvailabilityZone>>,
+
+    /// The AZ ID for the subnet, which names the same zone in every account. Conflicts with
+    /// `availability_zone`.
+    pub availability_zone_id: Option<Value<AvailabilityZoneId>>,
 
     /// Specify true to indicate that network interfaces created in the specified subnet should be assigned an IPv6 address.
-    pub assign_ipv6_address_on_creation: Option<bool>,
+    pub assign_ipv6_address_on_creation: Option<Value<bool>>,
 
-    /// The IPv6 network range for the subnet, in CIDR notation.
-    pub ipv6_cidr_block: Option<String>,
+    /// The IPv6 network range for the subnet, a /64 of the VPC's block. Use
+    /// [`Vpc::ipv6_subnet`] to derive it from the VPC's generated /56.
+    pub ipv6_cidr_block: Option<Value<cidr::Ipv6Block>>,
 
     /// Specify true to indicate that instances launched into the subnet should be assigned a public IP address.
-    pub map_public_ip_on_launch: Option<bool>,
+    pub map_public_ip_on_launch: Option<Value<bool>>,
 
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
 }
 
-impl<'a> From<Subnet<'a>> for Block {
-    fn from(subnet: Subnet<'a>) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_subnet")
-            .add_label(&subnet.name)
-            .add_attribute((
-                "vpc_id",
-                Expression::from(format!("${{{}.id}}", subnet.vpc.resource_name())),
-            ))
-            .add_attribute((
-                "cidr_block",
-                Expression::String(subnet.cidr_block.to_string()),
-            ));
+impl<'a> Subnet<'a> {
+    /// A subnet of `vpc` covering `cidr_block`, with every other argument left to its AWS
+    /// default.
+    pub fn new(name: &str, vpc: &'a Vpc, cidr_block: cidr::Block) -> Self {
+        Subnet {
+            name: name.to_string(),
+            vpc,
+            cidr_block: cidr_block.into(),
+            availability_zone: None,
+            availability_zone_id: None,
+            assign_ipv6_address_on_creation: None,
+            ipv6_cidr_block: None,
+            map_public_ip_on_launch: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
 
-        if let Some(az) = subnet.availability_zone {
-            block = block.add_attribute(("availability_zone", Expression::String(az.to_string())));
+    /// Checks the subnet arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
         }
 
-        if let Some(assign_ipv6) = subnet.assign_ipv6_address_on_creation {
-            block = block.add_attribute((
-                "assign_ipv6_address_on_creation",
-                Expression::Bool(assign_ipv6),
-            ));
+        if self.availability_zone.is_some() && self.availability_zone_id.is_some() {
+            return Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "availability_zone",
+                second: "availability_zone_id",
+            });
+        }
+
+        if let Some(cidr_block) = self.cidr_block.as_literal() {
+            let prefix_length = cidr_block.prefix_length();
+            if !(16..=28).contains(&prefix_length) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "cidr_block",
+                    format!(
+                        "prefix length must be between /16 and /28, got /{}",
+                        prefix_length
+                    ),
+                ));
+            }
         }
 
-        if let Some(ipv6_cidr) = subnet.ipv6_cidr_block {
-            block = block.add_attribute(("ipv6_cidr_block", Expression::String(ipv6_cidr)));
+        if let Some(ipv6_cidr_block) = self.ipv6_cidr_block.as_ref().and_then(Value::as_literal) {
+            if ipv6_cidr_block.prefix_length() != 64 {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "ipv6_cidr_block",
+                    format!(
+                        "prefix length must be /64, got /{}",
+                        ipv6_cidr_block.prefix_length()
+                    ),
+                ));
+            }
         }
 
-        if let Some(map_public_ip) = subnet.map_public_ip_on_launch {
-            block =
-                block.add_attribute(("map_public_ip_on_launch", Expression::Bool(map_public_ip)));
+        if let Some(true) = self
+            .assign_ipv6_address_on_creation
+            .as_ref()
+            .and_then(Value::as_literal)
+        {
+            if self.ipv6_cidr_block.is_none() {
+                return Err(Error::MissingArgument {
+                    resource: Self::TYPE,
+                    argument: "ipv6_cidr_block",
+                });
+            }
         }
 
-        if let Some(tags) = subnet.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
+        Ok(())
+    }
+
+    /// Checks that the subnet's zone is in the region of the provider that manages it.
+    pub fn validate_region(&self, provider: &AwsProvider) -> Result<(), Error> {
+        let (argument, region, name) = match (
+            self.availability_zone.as_ref().and_then(Value::as_literal),
+            self.availability_zone_id
+                .as_ref()
+                .and_then(Value::as_literal),
+        ) {
+            (Some(zone), _) => ("availability_zone", zone.region(), zone.to_string()),
+            (_, Some(id)) => ("availability_zone_id", id.region(), id.to_string()),
+            _ => return Ok(()),
+        };
+
+        if region != provider.region {
+            return Err(Error::invalid(
+                Self::TYPE,
+                argument,
+                format!("`{}` is not in provider region `{}`", name, provider.region),
+            ));
         }
 
-        block.build()
+        Ok(())
+    }
+
+    /// The ID of the subnet.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the subnet.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The association ID for the IPv6 CIDR block.
+    pub fn ipv6_cidr_block_association_id(&self) -> Reference {
+        self.attr("ipv6_cidr_block_association_id")
+    }
+
+    /// The ID of the AWS account that owns the subnet.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+
+    /// Outputs for the subnet ID and ARN, named `<name>_subnet_<attribute>`.
+    pub fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::new(format!("{}_subnet_id", self.name), self.id()),
+            Output::new(format!("{}_subnet_arn", self.name), self.arn()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::region::Region;
+    use hcl::Block;
+
+    #[test]
+    fn test_subnet_availability_zone_to_hcl() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let subnet = Subnet {
+            availability_zone_id: Some("use1-az4".parse::<AvailabilityZoneId>().unwrap().into()),
+            ..Subnet::new("private", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(subnet).unwrap()).unwrap();
+        assert!(hcl.contains(r#"availability_zone_id = "use1-az4""#));
+    }
+
+    #[test]
+    fn test_subnet_zone_must_be_in_provider_region() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let provider = AwsProvider::new(Region::UsEast1);
+
+        let subnet = Subnet {
+            availability_zone: Some(Region::UsEast1.availability_zones()[1].clone().into()),
+            ..Subnet::new("private", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+        assert!(subnet.validate_region(&provider).is_ok());
+
+        let subnet = Subnet {
+            availability_zone: Some("us-west-2a".parse::<AvailabilityZone>().unwrap().into()),
+            ..Subnet::new("private", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+        assert!(matches!(
+            subnet.validate_region(&provider),
+            Err(Error::InvalidArgument {
+                argument: "availability_zone",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_subnet_zone_conflicts_with_zone_id() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let subnet = Subnet {
+            availability_zone: Some(AvailabilityZone::new(Region::UsEast1, 'a').unwrap().into()),
+            availability_zone_id: Some(AvailabilityZoneId::new(Region::UsEast1, 1).into()),
+            ..Subnet::new("private", &vpc, "10.0.1.0/24".parse().unwrap())
+        };
+
+        assert!(matches!(
+            Block::try_from(subnet),
+            Err(Error::ConflictingArguments { .. })
+        ));
     }
 }
@@ -0,0 +1,376 @@
+use crate::aws::network::route::RouteTarget;
+use crate::aws::network::subnet::Subnet;
+use crate::aws::network::vpc::Vpc;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A transit gateway feature setting, rendered as `enable` or `disable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Toggle {
+    Enable,
+    Disable,
+}
+
+impl fmt::Display for Toggle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Toggle::Enable => write!(f, "enable"),
+            Toggle::Disable => write!(f, "disable"),
+        }
+    }
+}
+
+impl From<bool> for Toggle {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Toggle::Enable
+        } else {
+            Toggle::Disable
+        }
+    }
+}
+
+impl IntoExpression for Toggle {
+    fn into_expression(self) -> hcl::Expression {
+        hcl::Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(Toggle);
+
+/// Represents an AWS Transit Gateway resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_ec2_transit_gateway")]
+pub struct TransitGateway {
+    /// The name of the transit gateway.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The description of the transit gateway.
+    pub description: Option<Value<String>>,
+
+    /// The private ASN for the Amazon side of a BGP session, 64512-65534 or
+    /// 4200000000-4294967294.
+    pub amazon_side_asn: Option<Value<u32>>,
+
+    /// Accepts attachment requests from other accounts automatically.
+    pub auto_accept_shared_attachments: Option<Value<Toggle>>,
+
+    /// Associates new attachments with the default route table.
+    pub default_route_table_association: Option<Value<Toggle>>,
+
+    /// Propagates new attachments' routes to the default route table.
+    pub default_route_table_propagation: Option<Value<Toggle>>,
+
+    /// Resolves public DNS host names to private addresses across attached VPCs.
+    pub dns_support: Option<Value<Toggle>>,
+
+    /// Equal-cost multipath routing across VPN connections.
+    pub vpn_ecmp_support: Option<Value<Toggle>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl TransitGateway {
+    /// Checks the transit gateway arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        if let Some(asn) = self.amazon_side_asn.as_ref().and_then(Value::as_literal) {
+            if !(64512..=65534).contains(asn) && !(4200000000..=4294967294).contains(asn) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "amazon_side_asn",
+                    format!("{} is not a private ASN", asn),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the transit gateway.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// The ARN of the transit gateway.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// The ID of the default association route table.
+    pub fn association_default_route_table_id(&self) -> Reference {
+        self.attr("association_default_route_table_id")
+    }
+
+    /// The ID of the default propagation route table.
+    pub fn propagation_default_route_table_id(&self) -> Reference {
+        self.attr("propagation_default_route_table_id")
+    }
+}
+
+impl From<&TransitGateway> for RouteTarget {
+    fn from(gateway: &TransitGateway) -> Self {
+        RouteTarget::TransitGateway(gateway.id().into())
+    }
+}
+
+/// Attaches a VPC to an AWS Transit Gateway.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_ec2_transit_gateway_vpc_attachment")]
+pub struct TransitGatewayVpcAttachment<'a> {
+    /// The name of the attachment.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The transit gateway to attach to.
+    #[hcl(reference = "id")]
+    pub transit_gateway: &'a TransitGateway,
+
+    /// The VPC to attach.
+    #[hcl(reference = "id")]
+    pub vpc: &'a Vpc,
+
+    /// Subnets for the attachment's network interfaces, one per availability zone.
+    #[hcl(reference = "id", rename = "subnet_ids")]
+    pub subnets: Vec<&'a Subnet<'a>>,
+
+    /// Resolves public DNS host names to private addresses across attached VPCs.
+    pub dns_support: Option<Value<Toggle>>,
+
+    /// Routes IPv6 traffic through the attachment.
+    pub ipv6_support: Option<Value<Toggle>>,
+
+    /// Keeps both directions of a flow in the same availability zone, for stateful
+    /// appliances.
+    pub appliance_mode_support: Option<Value<Toggle>>,
+
+    /// Associates the attachment with the transit gateway's default route table.
+    pub transit_gateway_default_route_table_association: Option<Value<bool>>,
+
+    /// Propagates the attachment's routes to the transit gateway's default route table.
+    pub transit_gateway_default_route_table_propagation: Option<Value<bool>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl TransitGatewayVpcAttachment<'_> {
+    /// Checks the attachment arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.subnets.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "subnet_ids",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The ID of the attachment.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Represents a route table of an AWS Transit Gateway.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_ec2_transit_gateway_route_table")]
+pub struct TransitGatewayRouteTable<'a> {
+    /// The name of the route table.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The transit gateway the route table belongs to.
+    #[hcl(reference = "id")]
+    pub transit_gateway: &'a TransitGateway,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl TransitGatewayRouteTable<'_> {
+    /// Checks the route table arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)
+    }
+
+    /// The ID of the route table.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+/// Propagates an attachment's routes to a transit gateway route table.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_ec2_transit_gateway_route_table_propagation")]
+pub struct TransitGatewayRouteTablePropagation<'a> {
+    /// The name of the propagation.
+    #[hcl(label)]
+    pub name: String,
+
+    /// The attachment whose routes are propagated.
+    #[hcl(reference = "id", rename = "transit_gateway_attachment_id")]
+    pub attachment: &'a TransitGatewayVpcAttachment<'a>,
+
+    /// The route table the routes are propagated to.
+    #[hcl(reference = "id", rename = "transit_gateway_route_table_id")]
+    pub route_table: &'a TransitGatewayRouteTable<'a>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl TransitGatewayRouteTablePropagation<'_> {
+    /// Checks the propagation arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)
+    }
+
+    /// The ID of the propagation.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::cidr;
+    use crate::aws::network::route::{Route, RouteDestination, RouteTable};
+    use crate::stack::Stack;
+    use hcl::Block;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_transit_gateway_to_hcl() {
+        let vpc = Vpc::new("eks", "10.0.0.0/16".parse().unwrap());
+        let subnet = Subnet::new("transit", &vpc, "10.0.255.0/28".parse().unwrap());
+
+        let gateway = TransitGateway {
+            name: "hub".to_string(),
+            description: Some("Hub for the data and EKS VPCs".into()),
+            amazon_side_asn: Some(64512.into()),
+            auto_accept_shared_attachments: None,
+            default_route_table_association: Some(Toggle::Disable.into()),
+            default_route_table_propagation: Some(Toggle::Disable.into()),
+            dns_support: Some(Toggle::from(true).into()),
+            vpn_ecmp_support: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let attachment = TransitGatewayVpcAttachment {
+            name: "eks".to_string(),
+            transit_gateway: &gateway,
+            vpc: &vpc,
+            subnets: vec![&subnet],
+            dns_support: None,
+            ipv6_support: None,
+            appliance_mode_support: None,
+            transit_gateway_default_route_table_association: Some(false.into()),
+            transit_gateway_default_route_table_propagation: Some(false.into()),
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let tgw_route_table = TransitGatewayRouteTable {
+            name: "hub".to_string(),
+            transit_gateway: &gateway,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let propagation = TransitGatewayRouteTablePropagation {
+            name: "eks".to_string(),
+            attachment: &attachment,
+            route_table: &tgw_route_table,
+            meta: MetaArguments::default(),
+        };
+        let private = RouteTable {
+            name: "private".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let route = Route {
+            name: "private_data".to_string(),
+            route_table: &private,
+            destination: RouteDestination::Ipv4(
+                cidr::Block::new(Ipv4Addr::new(10, 1, 0, 0), 16)
+                    .unwrap()
+                    .into(),
+            ),
+            target: (&gateway).into(),
+            meta: MetaArguments::default(),
+        };
+
+        let mut stack = Stack::new();
+        stack
+            .add(gateway.clone())
+            .unwrap()
+            .add(attachment.clone())
+            .unwrap()
+            .add(tgw_route_table.clone())
+            .unwrap()
+            .add(propagation)
+            .unwrap()
+            .add(route)
+            .unwrap();
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_ec2_transit_gateway" "hub""#));
+        assert!(hcl.contains("amazon_side_asn = 64512"));
+        assert!(hcl.contains(r#"default_route_table_association = "disable""#));
+        assert!(hcl.contains(r#"dns_support = "enable""#));
+        assert!(hcl.contains("transit_gateway_id = aws_ec2_transit_gateway.hub.id"));
+        assert!(hcl.contains("aws_subnet.transit.id"));
+        assert!(hcl.contains("transit_gateway_default_route_table_association = false"));
+        assert!(hcl.contains(
+            "transit_gateway_attachment_id = aws_ec2_transit_gateway_vpc_attachment.eks.id"
+        ));
+        assert!(hcl.contains(
+            "transit_gateway_route_table_id = aws_ec2_transit_gateway_route_table.hub.id"
+        ));
+
+        let public_asn = TransitGateway {
+            amazon_side_asn: Some(16509.into()),
+            ..gateway.clone()
+        };
+        assert!(Block::try_from(public_asn).is_err());
+        assert!(Block::try_from(TransitGatewayVpcAttachment {
+            subnets: vec![],
+            ..attachment
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::aws::network::gateway::NAT;
+use crate::aws::network::network_acl::NetworkAclRule;
+use crate::aws::network::route::{AssociationTarget, Route, RouteTableAssociation, RouteTarget};
+use crate::aws::network::subnet::Subnet;
+use crate::error::{Error, Violation};
+use crate::reference::Resource;
+use crate::value::Value;
+
+/// A set of network resources checked against each other.
+///
+/// Each resource's own `validate` only sees its own arguments; this catches mistakes that
+/// span resources, such as a subnet outside its VPC, before `terraform apply` does. Only
+/// literal CIDR blocks are compared.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkResources<'a> {
+    pub subnets: Vec<&'a Subnet<'a>>,
+    pub nat_gateways: Vec<&'a NAT<'a>>,
+    pub routes: Vec<&'a Route<'a>>,
+    pub route_table_associations: Vec<&'a RouteTableAssociation<'a>>,
+    pub network_acl_rules: Vec<&'a NetworkAclRule<'a>>,
+}
+
+impl NetworkResources<'_> {
+    /// Every violation found, in the order the resources were listed.
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.check_subnets_in_vpc(&mut violations);
+        self.check_subnet_overlaps(&mut violations);
+        self.check_nat_gateways(&mut violations);
+        self.check_network_acl_rules(&mut violations);
+        violations
+    }
+
+    /// Fails with [`Error::Violations`] listing every violation, if there are any.
+    pub fn validate(&self) -> Result<(), Error> {
+        let violations = self.violations();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Violations(violations))
+        }
+    }
+
+    fn check_subnets_in_vpc(&self, violations: &mut Vec<Violation>) {
+        for subnet in &self.subnets {
+            if let (Some(cidr), Some(vpc_cidr)) = (
+                subnet.cidr_block.as_literal(),
+                subnet.vpc.cidr_block.as_literal(),
+            ) {
+                if !cidr.is_subnet_of(vpc_cidr) {
+                    violations.push(Violation {
+                        resources: vec![address(*subnet), address(subnet.vpc)],
+                        reason: format!(
+                            "subnet CIDR block {} is outside VPC CIDR block {}",
+                            cidr, vpc_cidr
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_subnet_overlaps(&self, violations: &mut Vec<Violation>) {
+        for (i, subnet) in self.subnets.iter().enumerate() {
+            for other in &self.subnets[i + 1..] {
+                if subnet.vpc.name != other.vpc.name {
+                    continue;
+                }
+                if let (Some(cidr), Some(other_cidr)) = (
+                    subnet.cidr_block.as_literal(),
+                    other.cidr_block.as_literal(),
+                ) {
+                    if cidr.overlaps(other_cidr) {
+                        violations.push(Violation {
+                            resources: vec![address(*subnet), address(*other)],
+                            reason: format!("CIDR blocks {} and {} overlap", cidr, other_cidr),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Public NAT gateways need a subnet whose route table sends traffic to an internet
+    /// gateway. Subnets without a listed association use the VPC's main route table,
+    /// which is not assumed to have one.
+    fn check_nat_gateways(&self, violations: &mut Vec<Violation>) {
+        for nat in &self.nat_gateways {
+            let private = nat
+                .connectivity_type
+                .as_ref()
+                .and_then(Value::as_literal)
+                .is_some_and(|kind| kind == "private");
+            if private {
+                continue;
+            }
+
+            let subnet_id = Value::from(nat.subnet.id());
+            let routed_to_internet = self
+                .route_table_associations
+                .iter()
+                .filter(|association| {
+                    matches!(&association.target, AssociationTarget::Subnet(id) if *id == subnet_id)
+                })
+                .any(|association| {
+                    self.routes.iter().any(|route| {
+                        route.route_table.name == association.route_table.name
+                            && matches!(route.target, RouteTarget::InternetGateway(_))
+                    })
+                });
+
+            if !routed_to_internet {
+                violations.push(Violation {
+                    resources: vec![address(*nat), address(nat.subnet)],
+                    reason: "public NAT gateway is in a subnet with no route to an internet \
+                             gateway"
+                        .to_string(),
+                });
+            }
+        }
+    }
+
+    /// Rules of one network ACL need distinct rule numbers in each direction.
+    fn check_network_acl_rules(&self, violations: &mut Vec<Violation>) {
+        for (i, rule) in self.network_acl_rules.iter().enumerate() {
+            for other in &self.network_acl_rules[i + 1..] {
+                if rule.network_acl.name != other.network_acl.name || rule.egress != other.egress {
+                    continue;
+                }
+                if let (Some(number), Some(other_number)) = (
+                    rule.rule_number.as_literal(),
+                    other.rule_number.as_literal(),
+                ) {
+                    if number == other_number {
+                        violations.push(Violation {
+                            resources: vec![address(*rule), address(*other)],
+                            reason: format!(
+                                "{} rule number {} is used more than once in {}",
+                                if rule.egress { "egress" } else { "ingress" },
+                                number,
+                                address(rule.network_acl)
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn address<R: Resource>(resource: &R) -> String {
+    resource.reference().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::cidr;
+    use crate::aws::network::gateway::Internet;
+    use crate::aws::network::route::{RouteDestination, RouteTable};
+    use crate::aws::network::vpc::{ElasticIp, Vpc};
+    use crate::meta::MetaArguments;
+
+    #[test]
+    fn test_network_reports_every_violation() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let public = Subnet::new("public", &vpc, "10.0.0.0/24".parse().unwrap());
+        let private = Subnet::new("private", &vpc, "10.0.0.0/23".parse().unwrap());
+        let outside = Subnet::new("outside", &vpc, "10.1.0.0/24".parse().unwrap());
+        let elastic_ip = ElasticIp::vpc("nat");
+        let nat = NAT {
+            id: Some("main".to_string()),
+            vpc: &vpc,
+            subnet: &private,
+            elastic_ip: Some(&elastic_ip),
+            connectivity_type: None,
+            tags: None,
+            state: None,
+            meta: MetaArguments::default(),
+        };
+
+        let network = NetworkResources {
+            subnets: vec![&public, &private, &outside],
+            nat_gateways: vec![&nat],
+            ..Default::default()
+        };
+        let violations = network.violations();
+
+        assert_eq!(violations.len(), 3);
+        assert_eq!(
+            violations[0].resources,
+            ["aws_subnet.outside", "aws_vpc.main"]
+        );
+        assert_eq!(
+            violations[1].resources,
+            ["aws_subnet.public", "aws_subnet.private"]
+        );
+        assert_eq!(
+            violations[2].resources,
+            ["aws_nat_gateway.main", "aws_subnet.private"]
+        );
+
+        let message = network.validate().unwrap_err().to_string();
+        assert!(message.starts_with("3 violation(s):"));
+        assert!(message.contains(
+            "aws_subnet.outside, aws_vpc.main: subnet CIDR block 10.1.0.0/24 is outside VPC \
+             CIDR block 10.0.0.0/16"
+        ));
+    }
+
+    #[test]
+    fn test_network_accepts_nat_in_public_subnet() {
+        let vpc = Vpc::new("main", "10.0.0.0/16".parse().unwrap());
+        let public = Subnet::new("public", &vpc, "10.0.0.0/24".parse().unwrap());
+        let private = Subnet::new("private", &vpc, "10.0.1.0/24".parse().unwrap());
+        let internet = Internet {
+            name: "main".to_string(),
+            vpc: &vpc,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let elastic_ip = ElasticIp::vpc("nat");
+        let nat = NAT {
+            id: Some("main".to_string()),
+            vpc: &vpc,
+            subnet: &public,
+            elastic_ip: Some(&elastic_ip),
+            connectivity_type: None,
+            tags: None,
+            state: None,
+            meta: MetaArguments::default(),
+        };
+        let route_table = RouteTable {
+            name: "public".to_string(),
+            vpc: &vpc,
+            propagating_vgws: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        let route = Route {
+            name: "public_internet".to_string(),
+            route_table: &route_table,
+            destination: RouteDestination::Ipv4("0.0.0.0/0".parse::<cidr::Block>().unwrap().into()),
+            target: (&internet).into(),
+            meta: MetaArguments::default(),
+        };
+        let association = RouteTableAssociation {
+            name: "public".to_string(),
+            target: (&public).into(),
+            route_table: &route_table,
+            meta: MetaArguments::default(),
+        };
+
+        let network = NetworkResources {
+            subnets: vec![&public, &private],
+            nat_gateways: vec![&nat],
+            routes: vec![&route],
+            route_table_associations: vec![&association],
+            ..Default::default()
+        };
+        assert!(network.validate().is_ok());
+    }
+}
@@ -1,101 +1,195 @@
 use crate::aws::network::cidr;
-use hcl::{Block, Expression, ObjectKey};
+use crate::error::Error;
+use crate::meta::MetaArguments;
+use crate::output::Output;
+use crate::reference::{DataSource, Reference, Resource};
+use crate::value::Value;
+use crate::HclResource;
+use hcl::expr::FuncCall;
+use hcl::{Block, Expression};
 use std::collections::HashMap;
 
 /// Represents an AWS VPC resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_vpc")]
 pub struct Vpc {
     /// The name of the VPC.
+    #[hcl(label)]
     pub name: String,
 
     /// The IPv4 CIDR block for the VPC.
-    pub cidr_block: cidr::Block,
+    pub cidr_block: Value<cidr::Block>,
 
     /// A tenancy option for instances launched into the VPC.
-    pub instance_tenancy: Option<String>,
+    pub instance_tenancy: Option<Value<String>>,
 
     /// A boolean flag to enable/disable DNS hostnames in the VPC.
-    pub enable_dns_hostnames: Option<bool>,
+    pub enable_dns_hostnames: Option<Value<bool>>,
 
     /// A boolean flag to enable/disable DNS support in the VPC.
-    pub enable_dns_support: Option<bool>,
+    pub enable_dns_support: Option<Value<bool>>,
 
     /// A boolean flag to enable/disable ClassicLink for the VPC.
-    pub enable_classiclink: Option<bool>,
+    pub enable_classiclink: Option<Value<bool>>,
 
     /// A boolean flag to enable/disable ClassicLink DNS Support for the VPC.
-    pub enable_classiclink_dns_support: Option<bool>,
+    pub enable_classiclink_dns_support: Option<Value<bool>>,
 
     /// Requests an Amazon-provided IPv6 CIDR block with a /56 prefix length for the VPC.
-    pub assign_generated_ipv6_cidr_block: Option<bool>,
+    pub assign_generated_ipv6_cidr_block: Option<Value<bool>>,
 
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
 }
 
 impl Vpc {
-    /// Method to get the HCL resource name
-    pub fn resource_name(&self) -> String {
-        format!("aws_vpc.{}", self.name)
+    /// A VPC covering `cidr_block`, with every other argument left to its AWS default.
+    pub fn new(name: &str, cidr_block: cidr::Block) -> Self {
+        Vpc {
+            name: name.to_string(),
+            cidr_block: cidr_block.into(),
+            instance_tenancy: None,
+            enable_dns_hostnames: None,
+            enable_dns_support: None,
+            enable_classiclink: None,
+            enable_classiclink_dns_support: None,
+            assign_generated_ipv6_cidr_block: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
     }
-}
 
-impl From<Vpc> for Block {
-    fn from(vpc: Vpc) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_vpc")
-            .add_label(&vpc.name)
-            .add_attribute(("cidr_block", Expression::String(vpc.cidr_block.to_string())));
+    /// Checks the VPC arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
 
-        if let Some(instance_tenancy) = vpc.instance_tenancy {
-            block = block.add_attribute(("instance_tenancy", Expression::String(instance_tenancy)));
+        if let Some(cidr_block) = self.cidr_block.as_literal() {
+            let prefix_length = cidr_block.prefix_length();
+            if !(16..=28).contains(&prefix_length) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "cidr_block",
+                    format!(
+                        "prefix length must be between /16 and /28, got /{}",
+                        prefix_length
+                    ),
+                ));
+            }
         }
 
-        if let Some(enable_dns_hostnames) = vpc.enable_dns_hostnames {
-            block = block.add_attribute((
+        if let (Some(true), Some(false)) = (
+            self.enable_dns_hostnames
+                .as_ref()
+                .and_then(Value::as_literal),
+            self.enable_dns_support.as_ref().and_then(Value::as_literal),
+        ) {
+            return Err(Error::invalid(
+                Self::TYPE,
                 "enable_dns_hostnames",
-                Expression::Bool(enable_dns_hostnames),
+                "DNS hostnames require `enable_dns_support`",
             ));
         }
 
-        if let Some(enable_dns_support) = vpc.enable_dns_support {
-            block =
-                block.add_attribute(("enable_dns_support", Expression::Bool(enable_dns_support)));
+        if let Some(instance_tenancy) = self.instance_tenancy.as_ref().and_then(Value::as_literal) {
+            if !["default", "dedicated"].contains(&instance_tenancy.as_str()) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "instance_tenancy",
+                    format!(
+                        "expected `default` or `dedicated`, got `{}`",
+                        instance_tenancy
+                    ),
+                ));
+            }
         }
 
-        if let Some(enable_classiclink) = vpc.enable_classiclink {
-            block =
-                block.add_attribute(("enable_classiclink", Expression::Bool(enable_classiclink)));
-        }
+        Ok(())
+    }
 
-        if let Some(enable_classiclink_dns_support) = vpc.enable_classiclink_dns_support {
-            block = block.add_attribute((
-                "enable_classiclink_dns_support",
-                Expression::Bool(enable_classiclink_dns_support),
-            ));
-        }
+    /// The ID of the VPC.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
 
-        if let Some(assign_generated_ipv6_cidr_block) = vpc.assign_generated_ipv6_cidr_block {
-            block = block.add_attribute((
-                "assign_generated_ipv6_cidr_block",
-                Expression::Bool(assign_generated_ipv6_cidr_block),
-            ));
-        }
+    /// Amazon Resource Name (ARN) of the VPC.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
 
-        if let Some(tags) = vpc.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
-        block.build()
+    /// The ID of the main route table associated with this VPC.
+    pub fn main_route_table_id(&self) -> Reference {
+        self.attr("main_route_table_id")
+    }
+
+    /// The ID of the network ACL created by default on VPC creation.
+    pub fn default_network_acl_id(&self) -> Reference {
+        self.attr("default_network_acl_id")
+    }
+
+    /// The ID of the security group created by default on VPC creation.
+    pub fn default_security_group_id(&self) -> Reference {
+        self.attr("default_security_group_id")
+    }
+
+    /// The ID of the route table created by default on VPC creation.
+    pub fn default_route_table_id(&self) -> Reference {
+        self.attr("default_route_table_id")
+    }
+
+    /// The association ID for the IPv6 CIDR block.
+    pub fn ipv6_association_id(&self) -> Reference {
+        self.attr("ipv6_association_id")
+    }
+
+    /// The IPv6 CIDR block assigned to the VPC.
+    pub fn ipv6_cidr_block(&self) -> Reference {
+        self.attr("ipv6_cidr_block")
+    }
+
+    /// The `netnum`th /64 of the VPC's generated /56, as a
+    /// `cidrsubnet(aws_vpc.<name>.ipv6_cidr_block, 8, <netnum>)` expression for a
+    /// dual-stack subnet's `ipv6_cidr_block`.
+    pub fn ipv6_subnet(&self, netnum: u8) -> Expression {
+        FuncCall::builder("cidrsubnet")
+            .arg(self.ipv6_cidr_block())
+            .arg(8)
+            .arg(netnum)
+            .build()
+            .into()
+    }
+
+    /// The ID of the AWS account that owns the VPC.
+    pub fn owner_id(&self) -> Reference {
+        self.attr("owner_id")
+    }
+
+    /// Outputs for the VPC ID, ARN and main route table, named `<name>_vpc_<attribute>`.
+    pub fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::new(format!("{}_vpc_id", self.name), self.id()),
+            Output::new(format!("{}_vpc_arn", self.name), self.arn()),
+            Output::new(
+                format!("{}_vpc_main_route_table_id", self.name),
+                self.main_route_table_id(),
+            ),
+        ]
     }
 }
 
 /// Represents a data source for an AWS VPC.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "aws_vpc")]
 pub struct VpcDataSource {
     /// The ID of the VPC.
     pub id: Option<String>,
@@ -116,6 +210,7 @@ pub struct VpcDataSource {
     pub tags: Option<HashMap<String, String>>,
 
     /// One or more name-value pairs to filter by.
+    #[hcl(block)]
     pub filter: Option<Vec<Filter>>,
 }
 
@@ -125,166 +220,177 @@ pub struct Filter {
     pub values: Vec<String>,
 }
 
-impl From<VpcDataSource> for Block {
-    fn from(data_source: VpcDataSource) -> Self {
-        let mut block = Block::builder("data")
-            .add_label("aws_vpc")
-            .add_label(data_source.id.as_deref().unwrap_or("vpc"));
-
-        if let Some(id) = data_source.id {
-            block = block.add_attribute(("id", Expression::String(id)));
-        }
-
-        if let Some(cidr_block) = data_source.cidr_block {
-            block = block.add_attribute(("cidr_block", Expression::String(cidr_block)));
-        }
-
-        if let Some(owner_id) = data_source.owner_id {
-            block = block.add_attribute(("owner_id", Expression::String(owner_id)));
-        }
-
-        if let Some(enable_dns_hostnames) = data_source.enable_dns_hostnames {
-            block = block.add_attribute((
-                "enable_dns_hostnames",
-                Expression::Bool(enable_dns_hostnames),
-            ));
-        }
+impl From<Filter> for Block {
+    fn from(filter: Filter) -> Self {
+        Block::builder("filter")
+            .add_attribute(("name", Expression::String(filter.name)))
+            .add_attribute((
+                "values",
+                Expression::Array(filter.values.into_iter().map(Expression::String).collect()),
+            ))
+            .build()
+    }
+}
 
-        if let Some(enable_dns_support) = data_source.enable_dns_support {
-            block =
-                block.add_attribute(("enable_dns_support", Expression::Bool(enable_dns_support)));
-        }
+impl DataSource for VpcDataSource {
+    const TYPE: &'static str = "aws_vpc";
 
-        if let Some(tags) = data_source.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
+    fn name(&self) -> &str {
+        self.id.as_deref().unwrap_or("vpc")
+    }
+}
 
-        if let Some(filters) = data_source.filter {
-            let filter_blocks: Vec<Block> = filters
-                .into_iter()
-                .map(|f| {
-                    Block::builder("filter")
-                        .add_attribute(("name", Expression::String(f.name)))
-                        .add_attribute((
-                            "values",
-                            Expression::Array(
-                                f.values.into_iter().map(Expression::String).collect(),
-                            ),
-                        ))
-                        .build()
-                })
-                .collect();
-            block = block.add_blocks(filter_blocks);
-        }
+impl VpcDataSource {
+    /// Amazon Resource Name (ARN) of the VPC.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
 
-        block.build()
+    /// The ID of the main route table associated with the VPC.
+    pub fn main_route_table_id(&self) -> Reference {
+        self.attr("main_route_table_id")
     }
 }
 
 /// Represents an AWS Elastic IP resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_eip")]
 pub struct ElasticIp {
     /// The name of the Elastic IP.
+    #[hcl(label)]
     pub name: String,
     /// EC2-VPC or EC2-Classic.
-    pub domain: Option<String>,
+    pub domain: Option<Value<String>>,
     /// Instance ID to associate with.
-    pub instance: Option<String>,
+    pub instance: Option<Value<String>>,
     /// Network interface ID to associate with.
-    pub network_interface: Option<String>,
+    pub network_interface: Option<Value<String>>,
     /// IP address from an EC2 BYOIP pool.
-    pub public_ipv4_pool: Option<String>,
+    pub public_ipv4_pool: Option<Value<String>>,
     /// Customer owned IPv4 address pool.
-    pub customer_owned_ipv4_pool: Option<String>,
+    pub customer_owned_ipv4_pool: Option<Value<String>>,
     /// Private IP address to associate with the Elastic IP address.
-    pub associate_with_private_ip: Option<String>,
+    pub associate_with_private_ip: Option<Value<String>>,
     /// Address of a VPC endpoint or NAT gateway.
-    pub address: Option<String>,
+    pub address: Option<Value<String>>,
     /// A map of tags to assign to the resource.
-    pub tags: Option<HashMap<String, String>>,
-}
+    pub tags: Option<Value<HashMap<String, String>>>,
 
-impl From<ElasticIp> for Block {
-    fn from(eip: ElasticIp) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_eip")
-            .add_label(&eip.name);
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
 
-        if let Some(domain) = eip.domain {
-            block = block.add_attribute(("domain", Expression::String(domain)));
+impl ElasticIp {
+    /// An Elastic IP for use in a VPC, such as by a NAT gateway, with every other argument
+    /// left to its AWS default.
+    pub fn vpc(name: &str) -> Self {
+        ElasticIp {
+            name: name.to_string(),
+            domain: Some("vpc".into()),
+            instance: None,
+            network_interface: None,
+            public_ipv4_pool: None,
+            customer_owned_ipv4_pool: None,
+            associate_with_private_ip: None,
+            address: None,
+            tags: None,
+            meta: MetaArguments::default(),
         }
+    }
 
-        if let Some(instance) = eip.instance {
-            block = block.add_attribute(("instance", Expression::String(instance)));
-        }
+    /// Checks the Elastic IP arguments against the constraints enforced by AWS.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
 
-        if let Some(network_interface) = eip.network_interface {
-            block =
-                block.add_attribute(("network_interface", Expression::String(network_interface)));
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
         }
 
-        if let Some(public_ipv4_pool) = eip.public_ipv4_pool {
-            block = block.add_attribute(("public_ipv4_pool", Expression::String(public_ipv4_pool)));
+        if let Some(domain) = self.domain.as_ref().and_then(Value::as_literal) {
+            if !["vpc", "standard"].contains(&domain.as_str()) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "domain",
+                    format!("expected `vpc` or `standard`, got `{}`", domain),
+                ));
+            }
         }
 
-        if let Some(customer_owned_ipv4_pool) = eip.customer_owned_ipv4_pool {
-            block = block.add_attribute((
-                "customer_owned_ipv4_pool",
-                Expression::String(customer_owned_ipv4_pool),
-            ));
+        if self.instance.is_some() && self.network_interface.is_some() {
+            return Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "instance",
+                second: "network_interface",
+            });
         }
 
-        if let Some(associate_with_private_ip) = eip.associate_with_private_ip {
-            block = block.add_attribute((
-                "associate_with_private_ip",
-                Expression::String(associate_with_private_ip),
-            ));
-        }
+        Ok(())
+    }
 
-        if let Some(address) = eip.address {
-            block = block.add_attribute(("address", Expression::String(address)));
-        }
+    /// The ID of the Elastic IP.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
 
-        if let Some(tags) = eip.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
+    /// ID that AWS assigns to represent the allocation of the Elastic IP address.
+    pub fn allocation_id(&self) -> Reference {
+        self.attr("allocation_id")
+    }
+
+    /// ID representing the association of the address with an instance or network interface.
+    pub fn association_id(&self) -> Reference {
+        self.attr("association_id")
+    }
 
-        block.build()
+    /// Contains the public IP address.
+    pub fn public_ip(&self) -> Reference {
+        self.attr("public_ip")
+    }
+
+    /// Public DNS associated with the Elastic IP address.
+    pub fn public_dns(&self) -> Reference {
+        self.attr("public_dns")
+    }
+
+    /// Contains the private IP address (if in VPC).
+    pub fn private_ip(&self) -> Reference {
+        self.attr("private_ip")
+    }
+
+    /// The Private DNS associated with the Elastic IP address (if in VPC).
+    pub fn private_dns(&self) -> Reference {
+        self.attr("private_dns")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::variable::{Type, Variable};
 
     #[test]
     fn test_vpc_to_hcl() {
         let vpc = Vpc {
-            name: "main".to_string(),
-            cidr_block: "10.0.0.0/16".to_string(),
-            instance_tenancy: Some("default".to_string()),
-            enable_dns_hostnames: Some(true),
-            enable_dns_support: Some(true),
-            enable_classiclink: None,
-            enable_classiclink_dns_support: None,
-            assign_generated_ipv6_cidr_block: Some(false),
-            tags: Some(HashMap::from([
-                ("Name".to_string(), "Main VPC".to_string()),
-                ("Environment".to_string(), "Production".to_string()),
-            ])),
+            instance_tenancy: Some("default".into()),
+            enable_dns_hostnames: Some(true.into()),
+            enable_dns_support: Some(true.into()),
+            assign_generated_ipv6_cidr_block: Some(false.into()),
+            tags: Some(
+                HashMap::from([
+                    ("Name".to_string(), "Main VPC".to_string()),
+                    ("Environment".to_string(), "Production".to_string()),
+                ])
+                .into(),
+            ),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
         };
 
-        let block: Block = vpc.into();
+        let outputs = vpc.outputs();
+        let block = Block::try_from(vpc.clone()).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
 
         assert!(hcl.contains("resource \"aws_vpc\" \"main\""));
@@ -294,9 +400,25 @@ mod tests {
         assert!(hcl.contains("enable_dns_support = true"));
         assert!(hcl.contains("assign_generated_ipv6_cidr_block = false"));
         assert!(hcl.contains("tags = {"));
-        assert!(hcl.contains("Name = \"Main VPC\""));
-        assert!(hcl.contains("Environment = \"Production\""));
-        assert!(hcl.contains("output \"id\" = aws_vpc.main.id"));
+        assert!(hcl.contains("\"Name\" = \"Main VPC\""));
+        assert!(hcl.contains("\"Environment\" = \"Production\""));
+
+        let output = Block::try_from(outputs[0].clone()).unwrap();
+        let hcl = hcl::to_string(&output).unwrap();
+        assert!(hcl.contains("output \"main_vpc_id\""));
+        assert!(hcl.contains("value = aws_vpc.main.id"));
+
+        let hostnames_without_dns = Vpc {
+            enable_dns_support: Some(false.into()),
+            ..vpc
+        };
+        assert!(matches!(
+            Block::try_from(hostnames_without_dns),
+            Err(Error::InvalidArgument {
+                argument: "enable_dns_hostnames",
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -327,9 +449,48 @@ mod tests {
         assert!(hcl.contains("enable_dns_hostnames = true"));
         assert!(hcl.contains("enable_dns_support = true"));
         assert!(hcl.contains("tags = {"));
-        assert!(hcl.contains("Name = \"Main VPC\""));
+        assert!(hcl.contains("\"Name\" = \"Main VPC\""));
         assert!(hcl.contains("filter {"));
         assert!(hcl.contains("name = \"tag:Environment\""));
-        assert!(hcl.contains("values = [\"Production\"]"));
+        assert!(hcl.contains(
+            r#"values = [
+      "Production"
+    ]"#
+        ));
+    }
+
+    #[test]
+    fn test_vpc_rejects_prefix_outside_aws_range() {
+        let vpc = Vpc::new("main", "10.0.0.0/8".parse().unwrap());
+
+        assert!(matches!(
+            Block::try_from(vpc),
+            Err(Error::InvalidArgument {
+                resource: "aws_vpc",
+                argument: "cidr_block",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_vpc_cidr_block_from_variable() {
+        let cidr_block = Variable {
+            name: "vpc_cidr".to_string(),
+            kind: Some(Type::String),
+            default: None,
+            description: None,
+            sensitive: None,
+            nullable: None,
+            validation: vec![],
+        };
+
+        let vpc = Vpc {
+            cidr_block: cidr_block.reference().into(),
+            ..Vpc::new("main", "10.0.0.0/16".parse().unwrap())
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(vpc).unwrap()).unwrap();
+        assert!(hcl.contains("cidr_block = var.vpc_cidr"));
     }
 }
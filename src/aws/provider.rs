@@ -1,15 +1,296 @@
 use crate::aws::region::Region;
-use hcl::{Block, Expression};
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::provider::Provider;
+use crate::value::Value;
+use hcl::{Block, Expression, Identifier};
+use std::collections::{BTreeMap, HashMap};
 
+/// Configuration for the `aws` provider. Add one per account and region, giving every
+/// configuration but the default an `alias`.
+#[derive(Debug, Clone)]
 pub struct AwsProvider {
+    /// Region where resources are managed.
     pub region: Region,
+
+    /// Name that distinguishes this configuration from the default one.
+    pub alias: Option<String>,
+
+    /// Named profile from the shared configuration and credentials files.
+    pub profile: Option<Value<String>>,
+
+    /// Paths to shared configuration files, instead of `~/.aws/config`.
+    pub shared_config_files: Vec<String>,
+
+    /// Role to assume for every API call.
+    pub assume_role: Option<AssumeRole>,
+
+    /// Tags applied to every resource managed by this configuration.
+    pub default_tags: Option<HashMap<String, String>>,
+
+    /// Account IDs the configuration may operate in, to guard against using the wrong
+    /// credentials.
+    pub allowed_account_ids: Vec<String>,
+
+    /// Custom endpoint URLs keyed by service name, such as `s3` or `dynamodb`.
+    pub endpoints: BTreeMap<String, String>,
+}
+
+/// Settings for the nested `assume_role` block.
+#[derive(Debug, Clone)]
+pub struct AssumeRole {
+    /// ARN of the role to assume.
+    pub role_arn: Value<String>,
+
+    /// Session name to use when assuming the role.
+    pub session_name: Option<Value<String>>,
+
+    /// External identifier to use when assuming the role.
+    pub external_id: Option<Value<String>>,
+}
+
+impl AwsProvider {
+    /// The default configuration for `region`, with no other arguments set.
+    pub fn new(region: Region) -> Self {
+        AwsProvider {
+            region,
+            alias: None,
+            profile: None,
+            shared_config_files: vec![],
+            assume_role: None,
+            default_tags: None,
+            allowed_account_ids: vec![],
+            endpoints: BTreeMap::new(),
+        }
+    }
+
+    /// Checks the alias and account IDs.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(alias) = &self.alias {
+            if Identifier::new(alias.as_str()).is_err() {
+                return Err(Error::invalid(
+                    "provider.aws",
+                    "alias",
+                    format!("`{}` is not a valid identifier", alias),
+                ));
+            }
+        }
+
+        for account_id in &self.allowed_account_ids {
+            if account_id.len() != 12 || !account_id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(Error::invalid(
+                    "provider.aws",
+                    "allowed_account_ids",
+                    format!("`{}` is not a 12 digit account ID", account_id),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Provider for AwsProvider {
+    const NAME: &'static str = "aws";
+    const SOURCE: &'static str = "hashicorp/aws";
+    const VERSION: &'static str = "~> 5.0";
+
+    fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+}
+
+impl TryFrom<AwsProvider> for Block {
+    type Error = Error;
+
+    fn try_from(provider: AwsProvider) -> Result<Self, Self::Error> {
+        provider.validate()?;
+
+        let mut block = Block::builder("provider")
+            .add_label(AwsProvider::NAME)
+            .add_attribute(("region", Expression::from(provider.region)));
+
+        if let Some(alias) = provider.alias {
+            block = block.add_attribute(("alias", Expression::String(alias)));
+        }
+
+        if let Some(profile) = provider.profile {
+            block = block.add_attribute(("profile", profile));
+        }
+
+        if !provider.shared_config_files.is_empty() {
+            block = block.add_attribute((
+                "shared_config_files",
+                Expression::Array(
+                    provider
+                        .shared_config_files
+                        .into_iter()
+                        .map(Expression::String)
+                        .collect(),
+                ),
+            ));
+        }
+
+        if !provider.allowed_account_ids.is_empty() {
+            block = block.add_attribute((
+                "allowed_account_ids",
+                Expression::Array(
+                    provider
+                        .allowed_account_ids
+                        .into_iter()
+                        .map(Expression::String)
+                        .collect(),
+                ),
+            ));
+        }
+
+        if let Some(assume_role) = provider.assume_role {
+            let mut assume_role_block =
+                Block::builder("assume_role").add_attribute(("role_arn", assume_role.role_arn));
+
+            if let Some(session_name) = assume_role.session_name {
+                assume_role_block = assume_role_block.add_attribute(("session_name", session_name));
+            }
+
+            if let Some(external_id) = assume_role.external_id {
+                assume_role_block = assume_role_block.add_attribute(("external_id", external_id));
+            }
+
+            block = block.add_block(assume_role_block.build());
+        }
+
+        if let Some(default_tags) = provider.default_tags {
+            let tags = default_tags.into_expression();
+            block = block.add_block(
+                Block::builder("default_tags")
+                    .add_attribute(("tags", tags))
+                    .build(),
+            );
+        }
+
+        if !provider.endpoints.is_empty() {
+            let endpoints = provider
+                .endpoints
+                .into_iter()
+                .fold(Block::builder("endpoints"), |block, (service, url)| {
+                    block.add_attribute((service, Expression::String(url)))
+                });
+            block = block.add_block(endpoints.build());
+        }
+
+        Ok(block.build())
+    }
 }
 
-impl From<AwsProvider> for Block {
-    fn from(provider: AwsProvider) -> Self {
-        Block::builder("provider")
-            .add_label("aws")
-            .add_attribute(("region", Expression::from(provider.region)))
-            .build()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::network::vpc::Vpc;
+    use crate::meta::MetaArguments;
+    use crate::stack::Stack;
+
+    #[test]
+    fn test_aws_provider_to_hcl() {
+        let block = Block::try_from(AwsProvider::new(Region::UsEast1)).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"provider "aws" {"#));
+        assert!(hcl.contains(r#"region = "us-east-1""#));
+    }
+
+    #[test]
+    fn test_aws_provider_requirement() {
+        let mut stack = Stack::new();
+        stack
+            .add_provider(AwsProvider::new(Region::UsWest2))
+            .unwrap();
+
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"required_providers {"#));
+        assert!(hcl.contains(r#"aws = {"#));
+        assert!(hcl.contains(r#"source = "hashicorp/aws""#));
+        assert!(hcl.contains(r#"version = "~> 5.0""#));
+    }
+
+    #[test]
+    fn test_aws_provider_with_all_arguments() {
+        let provider = AwsProvider {
+            alias: Some("production".to_string()),
+            profile: Some("deploy".into()),
+            shared_config_files: vec!["/etc/aws/config".to_string()],
+            assume_role: Some(AssumeRole {
+                role_arn: "arn:aws:iam::123456789012:role/deploy".into(),
+                session_name: Some("terraform".into()),
+                external_id: Some("acme".into()),
+            }),
+            default_tags: Some(HashMap::from([(
+                "ManagedBy".to_string(),
+                "terraform".to_string(),
+            )])),
+            allowed_account_ids: vec!["123456789012".to_string()],
+            endpoints: BTreeMap::from([("s3".to_string(), "http://localhost:4566".to_string())]),
+            ..AwsProvider::new(Region::UsEast1)
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(provider).unwrap()).unwrap();
+
+        assert!(hcl.contains(r#"alias = "production""#));
+        assert!(hcl.contains(r#"profile = "deploy""#));
+        assert!(hcl.contains(r#""/etc/aws/config""#));
+        assert!(hcl.contains(r#""123456789012""#));
+        assert!(hcl.contains("assume_role {"));
+        assert!(hcl.contains(r#"role_arn = "arn:aws:iam::123456789012:role/deploy""#));
+        assert!(hcl.contains(r#"session_name = "terraform""#));
+        assert!(hcl.contains(r#"external_id = "acme""#));
+        assert!(hcl.contains("default_tags {"));
+        assert!(hcl.contains(r#""ManagedBy" = "terraform""#));
+        assert!(hcl.contains("endpoints {"));
+        assert!(hcl.contains(r#"s3 = "http://localhost:4566""#));
+    }
+
+    #[test]
+    fn test_aws_provider_rejects_invalid_account_id() {
+        let provider = AwsProvider {
+            allowed_account_ids: vec!["1234".to_string()],
+            ..AwsProvider::new(Region::UsEast1)
+        };
+
+        assert!(matches!(
+            Block::try_from(provider),
+            Err(Error::InvalidArgument {
+                argument: "allowed_account_ids",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_resource_selects_aliased_provider() {
+        let west = AwsProvider {
+            alias: Some("west".to_string()),
+            ..AwsProvider::new(Region::UsWest2)
+        };
+
+        let vpc = Vpc {
+            meta: MetaArguments {
+                provider: Some(west.handle()),
+                ..Default::default()
+            },
+            ..Vpc::new("replica", "10.1.0.0/16".parse().unwrap())
+        };
+
+        let mut stack = Stack::new();
+        stack
+            .add_provider(AwsProvider::new(Region::UsEast1))
+            .unwrap()
+            .add_provider(west)
+            .unwrap()
+            .add(vpc)
+            .unwrap();
+
+        let hcl = stack.render().unwrap();
+        assert!(hcl.contains(r#"alias = "west""#));
+        assert!(hcl.contains("provider = aws.west"));
     }
 }
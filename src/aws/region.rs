@@ -1,19 +1,216 @@
+use crate::aws::availability_zone::AvailabilityZone;
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::value::impl_value_from;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Serialize, Deserialize)]
-pub enum Region {
-    UsWest2,
-    UsEast1,
+/// AWS partition: a group of regions with its own ARNs, endpoints and credentials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Partition {
+    /// Commercial regions.
+    Aws,
+    /// China regions.
+    AwsCn,
+    /// AWS GovCloud (US) regions.
+    AwsUsGov,
 }
 
-impl fmt::Display for Region {
+impl Partition {
+    /// Partition name as used in ARNs, e.g. `arn:aws-cn:s3:::bucket`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Partition::Aws => "aws",
+            Partition::AwsCn => "aws-cn",
+            Partition::AwsUsGov => "aws-us-gov",
+        }
+    }
+
+    /// DNS suffix of service endpoints in the partition.
+    pub fn dns_suffix(&self) -> &'static str {
+        match self {
+            Partition::Aws | Partition::AwsUsGov => "amazonaws.com",
+            Partition::AwsCn => "amazonaws.com.cn",
+        }
+    }
+
+    /// Service principal for trust policies, e.g. `ec2.amazonaws.com`.
+    pub fn service_principal(&self, service: &str) -> String {
+        format!("{}.{}", service, self.dns_suffix())
+    }
+
+    /// Builds an ARN in this partition. `region` and `account_id` are empty for global
+    /// resources such as S3 buckets and IAM roles respectively.
+    pub fn arn(&self, service: &str, region: &str, account_id: &str, resource: &str) -> String {
+        format!(
+            "arn:{}:{}:{}:{}:{}",
+            self.as_str(),
+            service,
+            region,
+            account_id,
+            resource
+        )
+    }
+}
+
+impl fmt::Display for Partition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+macro_rules! regions {
+    ($($variant:ident => $code:literal, $zones:literal,)*) => {
+        /// An AWS region.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum Region {
+            $(
+                #[serde(rename = $code)]
+                $variant,
+            )*
+        }
+
+        impl Region {
+            /// Every region, in the order they are declared.
+            pub const ALL: &'static [Region] = &[$(Region::$variant,)*];
+
+            /// Region code, such as `us-east-1`.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Region::$variant => $code,)*
+                }
+            }
+
+            /// Letters of the availability zones generally available in the region.
+            fn zone_letters(&self) -> &'static str {
+                match self {
+                    $(Region::$variant => $zones,)*
+                }
+            }
+        }
+
+        impl FromStr for Region {
+            type Err = Error;
+
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                match code {
+                    $($code => Ok(Region::$variant),)*
+                    _ => Err(Error::invalid(
+                        "region",
+                        "region",
+                        format!("unknown region `{}`", code),
+                    )),
+                }
+            }
+        }
+    };
+}
+
+regions! {
+    UsEast1 => "us-east-1", "abcdef",
+    UsEast2 => "us-east-2", "abc",
+    UsWest1 => "us-west-1", "ac",
+    UsWest2 => "us-west-2", "abcd",
+    AfSouth1 => "af-south-1", "abc",
+    ApEast1 => "ap-east-1", "abc",
+    ApEast2 => "ap-east-2", "abc",
+    ApSouth1 => "ap-south-1", "abc",
+    ApSouth2 => "ap-south-2", "abc",
+    ApNortheast1 => "ap-northeast-1", "acd",
+    ApNortheast2 => "ap-northeast-2", "abcd",
+    ApNortheast3 => "ap-northeast-3", "abc",
+    ApSoutheast1 => "ap-southeast-1", "abc",
+    ApSoutheast2 => "ap-southeast-2", "abc",
+    ApSoutheast3 => "ap-southeast-3", "abc",
+    ApSoutheast4 => "ap-southeast-4", "abc",
+    ApSoutheast5 => "ap-southeast-5", "abc",
+    ApSoutheast6 => "ap-southeast-6", "abc",
+    ApSoutheast7 => "ap-southeast-7", "abc",
+    CaCentral1 => "ca-central-1", "abd",
+    CaWest1 => "ca-west-1", "abc",
+    EuCentral1 => "eu-central-1", "abc",
+    EuCentral2 => "eu-central-2", "abc",
+    EuWest1 => "eu-west-1", "abc",
+    EuWest2 => "eu-west-2", "abc",
+    EuWest3 => "eu-west-3", "abc",
+    EuSouth1 => "eu-south-1", "abc",
+    EuSouth2 => "eu-south-2", "abc",
+    EuNorth1 => "eu-north-1", "abc",
+    IlCentral1 => "il-central-1", "abc",
+    MeSouth1 => "me-south-1", "abc",
+    MeCentral1 => "me-central-1", "abc",
+    MxCentral1 => "mx-central-1", "abc",
+    SaEast1 => "sa-east-1", "abc",
+    UsGovEast1 => "us-gov-east-1", "abc",
+    UsGovWest1 => "us-gov-west-1", "abc",
+    CnNorth1 => "cn-north-1", "abd",
+    CnNorthwest1 => "cn-northwest-1", "abc",
+}
+
+impl Region {
+    /// Partition the region belongs to.
+    pub fn partition(&self) -> Partition {
         match self {
-            Region::UsWest2 => write!(f, "us-west-2"),
-            Region::UsEast1 => write!(f, "us-east-1"),
+            Region::UsGovEast1 | Region::UsGovWest1 => Partition::AwsUsGov,
+            Region::CnNorth1 | Region::CnNorthwest1 => Partition::AwsCn,
+            _ => Partition::Aws,
         }
     }
+
+    /// DNS suffix of service endpoints in the region's partition.
+    pub fn dns_suffix(&self) -> &'static str {
+        self.partition().dns_suffix()
+    }
+
+    /// Regional endpoint host name of a service, e.g. `ec2.eu-west-1.amazonaws.com`.
+    pub fn endpoint(&self, service: &str) -> String {
+        format!("{}.{}.{}", service, self.as_str(), self.dns_suffix())
+    }
+
+    /// Availability zones generally available in the region, excluding Local and
+    /// Wavelength zones. Accounts may be granted access to others.
+    pub fn availability_zones(&self) -> Vec<AvailabilityZone> {
+        self.zone_letters()
+            .chars()
+            .map(|letter| AvailabilityZone::new(*self, letter).expect("lowercase zone letter"))
+            .collect()
+    }
+
+    /// Prefix of the region's zone IDs, e.g. `use1` for `us-east-1` and `usge1` for
+    /// `us-gov-east-1`.
+    pub fn zone_id_prefix(&self) -> String {
+        self.as_str()
+            .split('-')
+            .map(|part| match part {
+                "gov" => "g",
+                "north" => "n",
+                "south" => "s",
+                "east" => "e",
+                "west" => "w",
+                "central" => "c",
+                "northeast" => "ne",
+                "northwest" => "nw",
+                "southeast" => "se",
+                "southwest" => "sw",
+                other => other,
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&str> for Region {
+    type Error = Error;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl From<Region> for hcl::Expression {
@@ -21,3 +218,87 @@ impl From<Region> for hcl::Expression {
         hcl::Expression::String(region.to_string())
     }
 }
+
+impl IntoExpression for Region {
+    fn into_expression(self) -> hcl::Expression {
+        self.into()
+    }
+}
+
+impl_value_from!(Region);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_round_trips_through_code() {
+        for region in Region::ALL {
+            assert_eq!(region.as_str().parse::<Region>().unwrap(), *region);
+        }
+    }
+
+    #[test]
+    fn test_unknown_region() {
+        assert!(matches!(
+            Region::try_from("us-north-9"),
+            Err(Error::InvalidArgument {
+                resource: "region",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_region_partition() {
+        assert_eq!(Region::UsEast1.partition(), Partition::Aws);
+        assert_eq!(Region::UsGovWest1.partition(), Partition::AwsUsGov);
+        assert_eq!(Region::CnNorthwest1.partition(), Partition::AwsCn);
+        assert_eq!(Region::CnNorth1.dns_suffix(), "amazonaws.com.cn");
+        assert_eq!(
+            Region::CnNorth1.endpoint("ec2"),
+            "ec2.cn-north-1.amazonaws.com.cn"
+        );
+    }
+
+    #[test]
+    fn test_region_availability_zones() {
+        let zones: Vec<String> = Region::CaCentral1
+            .availability_zones()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(zones, ["ca-central-1a", "ca-central-1b", "ca-central-1d"]);
+        assert!(Region::ALL
+            .iter()
+            .all(|region| !region.availability_zones().is_empty()));
+        assert_eq!(Region::UsGovEast1.zone_id_prefix(), "usge1");
+        assert_eq!(Region::CnNorthwest1.zone_id_prefix(), "cnnw1");
+    }
+
+    #[test]
+    fn test_partition_arns_and_principals() {
+        let partition = Region::UsGovEast1.partition();
+
+        assert_eq!(
+            partition.arn("s3", "", "", "state-bucket"),
+            "arn:aws-us-gov:s3:::state-bucket"
+        );
+        assert_eq!(
+            Partition::AwsCn.service_principal("ec2"),
+            "ec2.amazonaws.com.cn"
+        );
+    }
+
+    #[test]
+    fn test_region_serializes_as_code() {
+        #[derive(Serialize, Deserialize)]
+        struct Config {
+            region: Region,
+        }
+
+        let config: Config = hcl::from_str(r#"region = "eu-west-1""#).unwrap();
+        assert_eq!(config.region, Region::EuWest1);
+    }
+}
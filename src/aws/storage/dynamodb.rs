@@ -0,0 +1,223 @@
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::{Block, Expression};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Controls how you are charged for read and write throughput.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BillingMode {
+    Provisioned,
+    PayPerRequest,
+}
+
+impl fmt::Display for BillingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BillingMode::Provisioned => write!(f, "PROVISIONED"),
+            BillingMode::PayPerRequest => write!(f, "PAY_PER_REQUEST"),
+        }
+    }
+}
+
+impl IntoExpression for BillingMode {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(BillingMode);
+
+/// Scalar type of a key attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeType {
+    String,
+    Number,
+    Binary,
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeType::String => write!(f, "S"),
+            AttributeType::Number => write!(f, "N"),
+            AttributeType::Binary => write!(f, "B"),
+        }
+    }
+}
+
+/// An attribute used as a table or index key.
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    /// Name of the attribute.
+    pub name: String,
+    /// Attribute type.
+    pub kind: AttributeType,
+}
+
+impl From<Attribute> for Block {
+    fn from(attribute: Attribute) -> Self {
+        Block::builder("attribute")
+            .add_attribute(("name", Expression::String(attribute.name)))
+            .add_attribute(("type", Expression::String(attribute.kind.to_string())))
+            .build()
+    }
+}
+
+/// Represents an AWS DynamoDB table resource.
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_dynamodb_table")]
+pub struct Table {
+    /// Unique within a region name of the table.
+    #[hcl(label, rename = "name")]
+    pub name: String,
+
+    /// Controls how you are charged for read and write throughput. Defaults to `PROVISIONED`.
+    pub billing_mode: Option<Value<BillingMode>>,
+
+    /// Attribute to use as the hash (partition) key.
+    pub hash_key: String,
+
+    /// Attribute to use as the range (sort) key.
+    pub range_key: Option<String>,
+
+    /// Key attributes. Every key used by the table must be listed here.
+    #[hcl(block)]
+    pub attributes: Vec<Attribute>,
+
+    /// Number of read units for this table. Required when `billing_mode` is `PROVISIONED`.
+    pub read_capacity: Option<Value<u32>>,
+
+    /// Number of write units for this table. Required when `billing_mode` is `PROVISIONED`.
+    pub write_capacity: Option<Value<u32>>,
+
+    /// Whether deletion protection is enabled.
+    pub deletion_protection_enabled: Option<Value<bool>>,
+
+    /// A map of tags to assign to the resource.
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
+
+impl Table {
+    /// A table in the shape Terraform's S3 backend expects for state locking: on-demand
+    /// billing with a string `LockID` hash key.
+    pub fn state_lock(name: &str) -> Self {
+        Table {
+            name: name.to_string(),
+            billing_mode: Some(BillingMode::PayPerRequest.into()),
+            hash_key: "LockID".to_string(),
+            range_key: None,
+            attributes: vec![Attribute {
+                name: "LockID".to_string(),
+                kind: AttributeType::String,
+            }],
+            read_capacity: None,
+            write_capacity: None,
+            deletion_protection_enabled: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        }
+    }
+
+    /// Checks the table arguments against the constraints enforced by DynamoDB.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        if self.name.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "name",
+            });
+        }
+
+        let keys = std::iter::once(&self.hash_key).chain(self.range_key.as_ref());
+        for key in keys {
+            if !self.attributes.iter().any(|a| &a.name == key) {
+                return Err(Error::invalid(
+                    Self::TYPE,
+                    "attribute",
+                    format!("key `{}` is not defined as an attribute", key),
+                ));
+            }
+        }
+
+        let provisioned = matches!(
+            self.billing_mode.as_ref().map(Value::as_literal),
+            None | Some(Some(BillingMode::Provisioned))
+        );
+        if provisioned && (self.read_capacity.is_none() || self.write_capacity.is_none()) {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "read_capacity",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Name of the table.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
+
+    /// ARN of the table.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
+
+    /// ARN of the table stream, if enabled.
+    pub fn stream_arn(&self) -> Reference {
+        self.attr("stream_arn")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_lock_table_to_hcl() {
+        let block = Block::try_from(Table::state_lock("terraform-locks")).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"resource "aws_dynamodb_table" "terraform-locks" {"#));
+        assert!(hcl.contains(r#"billing_mode = "PAY_PER_REQUEST""#));
+        assert!(hcl.contains(r#"hash_key = "LockID""#));
+        assert!(hcl.contains(r#"attribute {"#));
+        assert!(hcl.contains(r#"type = "S""#));
+    }
+
+    #[test]
+    fn test_table_requires_key_attribute() {
+        let table = Table {
+            attributes: vec![],
+            ..Table::state_lock("terraform-locks")
+        };
+
+        assert!(matches!(
+            Block::try_from(table),
+            Err(Error::InvalidArgument {
+                argument: "attribute",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_provisioned_table_requires_capacity() {
+        let table = Table {
+            billing_mode: Some(BillingMode::Provisioned.into()),
+            ..Table::state_lock("terraform-locks")
+        };
+
+        assert!(Block::try_from(table).is_err());
+    }
+}
@@ -1 +1,2 @@
+pub mod dynamodb;
 pub mod s3;
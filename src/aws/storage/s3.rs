@@ -1,7 +1,14 @@
-use hcl::{Block, Expression, ObjectKey};
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::meta::MetaArguments;
+use crate::reference::{Reference, Resource};
+use crate::value::{impl_value_from, Value};
+use crate::HclResource;
+use hcl::{Block, Expression};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::net::Ipv4Addr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ACL {
@@ -19,6 +26,19 @@ pub enum AccessControlPolicy {
     ACL,
 }
 
+impl From<AccessControlPolicy> for Block {
+    fn from(policy: AccessControlPolicy) -> Self {
+        let kind = match policy {
+            AccessControlPolicy::AccessControlPolicy => "access_control_policy",
+            AccessControlPolicy::ACL => "acl",
+        };
+
+        Block::builder("access_control_policy")
+            .add_attribute(("type", Expression::String(kind.to_string())))
+            .build()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ACLOptions {
     Private,
@@ -44,147 +64,265 @@ impl fmt::Display for ACLOptions {
     }
 }
 
+impl IntoExpression for ACLOptions {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl_value_from!(ACLOptions);
+
 /// Represents an AWS S3 bucket ACL resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_s3_bucket_acl")]
 pub struct BucketACL {
     /// Canned ACL to apply to the bucket.
     /// Optional, but one of `acl` or `access_control_policy` is required.
-    pub acl: Option<ACLOptions>,
+    pub acl: Option<Value<ACLOptions>>,
 
     /// Configuration block that sets the ACL permissions for an object per grantee.
     /// Optional, but one of `access_control_policy` or `acl` is required.
+    #[hcl(block)]
     pub access_control_policy: Option<AccessControlPolicy>,
 
     /// Bucket to which to apply the ACL.
+    #[hcl(label, rename = "bucket")]
     pub bucket: String,
 
     /// Account ID of the expected bucket owner.
-    pub expected_bucket_owner: Option<String>,
-}
+    pub expected_bucket_owner: Option<Value<String>>,
 
-impl From<BucketACL> for Block {
-    fn from(bucket_acl: BucketACL) -> Self {
-        let mut block = Block::builder("resource")
-            .add_label("aws_s3_bucket_acl")
-            .add_label(&bucket_acl.bucket)
-            .add_attribute(("bucket", Expression::String(bucket_acl.bucket)));
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
+}
 
-        if let Some(acl) = bucket_acl.acl {
-            block = block.add_attribute(("acl", Expression::String(acl.to_string())));
-        }
+impl BucketACL {
+    /// Checks that exactly one of `acl` and `access_control_policy` is set.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
 
-        if let Some(access_control_policy) = bucket_acl.access_control_policy {
-            let mut acp_block = Block::builder("access_control_policy");
-
-            match access_control_policy {
-                AccessControlPolicy::AccessControlPolicy => {
-                    acp_block = acp_block.add_attribute((
-                        "type",
-                        Expression::String("access_control_policy".to_string()),
-                    ));
-                }
-                AccessControlPolicy::ACL => {
-                    acp_block =
-                        acp_block.add_attribute(("type", Expression::String("acl".to_string())));
-                }
-            }
-
-            block = block.add_block(acp_block.build());
+        if self.bucket.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "bucket",
+            });
         }
 
-        if let Some(expected_bucket_owner) = bucket_acl.expected_bucket_owner {
-            block = block.add_attribute((
-                "expected_bucket_owner",
-                Expression::String(expected_bucket_owner),
-            ));
+        match (&self.acl, &self.access_control_policy) {
+            (Some(_), Some(_)) => Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "acl",
+                second: "access_control_policy",
+            }),
+            (None, None) => Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "acl",
+            }),
+            _ => Ok(()),
         }
+    }
 
-        block.build()
+    /// The bucket, expected_bucket_owner (if configured), and acl (if configured) separated by commas.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
     }
 }
 
 /// Represents an AWS S3 bucket resource.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "aws_s3_bucket")]
 pub struct Bucket {
     /// Name of the bucket. If omitted, Terraform will assign a random, unique name.
     /// Must be lowercase and less than or equal to 63 characters in length.
     /// The name must not be in the format [bucket_name]--[azid]--x-s3.
     /// Use the aws_s3_directory_bucket resource to manage S3 Express buckets.
+    #[hcl(rename = "bucket")]
     pub name: Option<String>,
 
     /// Access Control List (ACL) for the bucket.
     /// Defaults to "private".
-    pub acl: Option<ACLOptions>,
+    #[hcl(default = "Value::Literal(ACLOptions::Private)")]
+    pub acl: Option<Value<ACLOptions>>,
 
     /// Creates a unique bucket name beginning with the specified prefix.
     /// Conflicts with `bucket`.
     /// Must be lowercase and less than or equal to 37 characters in length.
+    #[hcl(rename = "bucket_prefix")]
     pub prefix: Option<String>,
 
     /// Boolean that indicates all objects (including any locked objects) should be deleted
     /// from the bucket when the bucket is destroyed so that the bucket can be destroyed without error.
     /// These objects are not recoverable. This only deletes objects when the bucket is destroyed,
     /// not when setting this parameter to true.
-    pub force_destroy: Option<bool>,
+    pub force_destroy: Option<Value<bool>>,
 
     /// Indicates whether this bucket has an Object Lock configuration enabled.
     /// Valid values are true or false.
     /// This argument is not supported in all regions or partitions.
-    pub object_lock_enabled: Option<bool>,
+    pub object_lock_enabled: Option<Value<bool>>,
 
     /// Map of tags to assign to the bucket.
     /// If configured with a provider default_tags configuration block present,
     /// tags with matching keys will overwrite those defined at the provider-level.
-    pub tags: Option<HashMap<String, String>>,
+    pub tags: Option<Value<HashMap<String, String>>>,
+
+    /// Meta-arguments such as `count`, `depends_on` and `lifecycle`.
+    #[hcl(meta)]
+    pub meta: MetaArguments,
 }
 
-impl From<Bucket> for Block {
-    fn from(bucket: Bucket) -> Self {
-        let mut block = Block::builder("resource").add_label("aws_s3_bucket");
+impl Resource for Bucket {
+    const TYPE: &'static str = "aws_s3_bucket";
+
+    /// The bucket name, falling back to the prefix for buckets with generated names. Dotted
+    /// or digit-leading names are sanitized into the block label and references, e.g.
+    /// `logs.example.com` is labelled `logs_example_com`.
+    fn name(&self) -> &str {
+        self.name
+            .as_deref()
+            .or(self.prefix.as_deref())
+            .unwrap_or_default()
+    }
+}
 
-        if let Some(name) = &bucket.name {
-            block = block
-                .add_label(name)
-                .add_attribute(("bucket", Expression::String(name.clone())));
+impl Bucket {
+    /// Checks that exactly one of `name` and `prefix` is set and that it follows the S3
+    /// naming rules.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+
+        match (&self.name, &self.prefix) {
+            (Some(_), Some(_)) => Err(Error::ConflictingArguments {
+                resource: Self::TYPE,
+                first: "bucket",
+                second: "bucket_prefix",
+            }),
+            (None, None) => Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "bucket",
+            }),
+            (Some(name), None) => validate_bucket_name(name),
+            (None, Some(prefix)) => validate_bucket_prefix(prefix),
         }
+    }
 
-        match bucket.acl {
-            Some(acl) => block = block.add_attribute(("acl", Expression::String(acl.to_string()))),
-            None => {
-                block = block
-                    .add_attribute(("acl", Expression::String(ACLOptions::Private.to_string())))
-            }
-        }
+    /// Name of the bucket.
+    pub fn id(&self) -> Reference {
+        self.attr("id")
+    }
 
-        if let Some(prefix) = bucket.prefix {
-            block = block.add_attribute(("bucket_prefix", Expression::String(prefix)));
-        }
+    /// ARN of the bucket. Will be of format `arn:aws:s3:::bucketname`.
+    pub fn arn(&self) -> Reference {
+        self.attr("arn")
+    }
 
-        if let Some(force_destroy) = bucket.force_destroy {
-            block = block.add_attribute(("force_destroy", Expression::Bool(force_destroy)));
-        }
+    /// Bucket domain name. Will be of format `bucketname.s3.amazonaws.com`.
+    pub fn bucket_domain_name(&self) -> Reference {
+        self.attr("bucket_domain_name")
+    }
 
-        if let Some(object_lock_enabled) = bucket.object_lock_enabled {
-            block =
-                block.add_attribute(("object_lock_enabled", Expression::Bool(object_lock_enabled)));
-        }
+    /// The bucket region-specific domain name.
+    pub fn bucket_regional_domain_name(&self) -> Reference {
+        self.attr("bucket_regional_domain_name")
+    }
 
-        if let Some(tags) = bucket.tags {
-            let tags_expr = Expression::Object(
-                tags.into_iter()
-                    .map(|(k, v)| (ObjectKey::from(k), Expression::String(v)))
-                    .collect(),
-            );
-            block = block.add_attribute(("tags", tags_expr));
-        }
+    /// Route 53 Hosted Zone ID for this bucket's region.
+    pub fn hosted_zone_id(&self) -> Reference {
+        self.attr("hosted_zone_id")
+    }
+
+    /// AWS region this bucket resides in.
+    pub fn region(&self) -> Reference {
+        self.attr("region")
+    }
+}
+
+/// Checks a full bucket name: 3 to 63 characters of lowercase letters, digits, `.` and `-`,
+/// starting and ending with a letter or digit, with no `..` and not formatted as an IPv4
+/// address.
+fn validate_bucket_name(name: &str) -> Result<(), Error> {
+    if !(3..=63).contains(&name.len()) {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            "bucket",
+            "must be between 3 and 63 characters",
+        ));
+    }
+
+    validate_bucket_characters("bucket", name)?;
+
+    if !name.ends_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            "bucket",
+            "must end with a letter or digit",
+        ));
+    }
+
+    if name.parse::<Ipv4Addr>().is_ok() {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            "bucket",
+            "must not be formatted as an IP address",
+        ));
+    }
+
+    Ok(())
+}
 
-        block.build()
+/// Checks a bucket name prefix, which follows the same rules as a name except that Terraform
+/// completes it, so it may be short and end in any allowed character.
+fn validate_bucket_prefix(prefix: &str) -> Result<(), Error> {
+    if prefix.is_empty() || prefix.len() > 37 {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            "bucket_prefix",
+            "must be between 1 and 37 characters",
+        ));
     }
+
+    validate_bucket_characters("bucket_prefix", prefix)
+}
+
+fn validate_bucket_characters(argument: &'static str, name: &str) -> Result<(), Error> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| !matches!(c, 'a'..='z' | '0'..='9' | '.' | '-'))
+    {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            argument,
+            format!(
+                "may only contain lowercase letters, digits, `.` and `-`, found `{}`",
+                c
+            ),
+        ));
+    }
+
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            argument,
+            "must start with a letter or digit",
+        ));
+    }
+
+    if name.contains("..") {
+        return Err(Error::invalid(
+            Bucket::TYPE,
+            argument,
+            "must not contain consecutive periods",
+        ));
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::meta::Lifecycle;
 
     use hcl;
 
@@ -197,13 +335,40 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-bucket" {"#));
         assert!(hcl.contains(r#"bucket = "my-bucket""#));
     }
 
+    #[test]
+    fn test_bucket_with_prevent_destroy() {
+        let bucket = Bucket {
+            name: Some("acme-terraform-state".to_string()),
+            prefix: None,
+            acl: None,
+            force_destroy: None,
+            object_lock_enabled: None,
+            tags: None,
+            meta: MetaArguments {
+                lifecycle: Some(Lifecycle {
+                    prevent_destroy: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        };
+        let block = Block::try_from(bucket).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(
+            r#"  lifecycle {
+    prevent_destroy = true
+  }"#
+        ));
+    }
+
     #[test]
     fn test_bucket_with_prefix() {
         let bucket = Bucket {
@@ -213,9 +378,11 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-prefix-" {"#));
         assert!(hcl.contains(r#"bucket_prefix = "my-prefix-""#));
     }
 
@@ -225,11 +392,12 @@ mod tests {
             name: Some("my-bucket".to_string()),
             prefix: None,
             acl: None,
-            force_destroy: Some(true),
+            force_destroy: Some(true.into()),
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"force_destroy = true"#));
     }
@@ -241,10 +409,11 @@ mod tests {
             prefix: None,
             acl: None,
             force_destroy: None,
-            object_lock_enabled: Some(true),
+            object_lock_enabled: Some(true.into()),
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"object_lock_enabled = true"#));
     }
@@ -260,9 +429,10 @@ mod tests {
             acl: None,
             force_destroy: None,
             object_lock_enabled: None,
-            tags: Some(tags),
+            tags: Some(tags.into()),
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"tags = {"#));
         assert!(hcl.contains(r#""Environment" = "Production""#));
@@ -275,17 +445,18 @@ mod tests {
         tags.insert("Environment".to_string(), "Production".to_string());
         let bucket = Bucket {
             name: Some("my-bucket".to_string()),
-            prefix: Some("prefix-".to_string()),
-            acl: None,
-            force_destroy: Some(true),
-            object_lock_enabled: Some(false),
-            tags: Some(tags),
+            prefix: None,
+            acl: Some(ACLOptions::BucketOwnerFullControl.into()),
+            force_destroy: Some(true.into()),
+            object_lock_enabled: Some(false.into()),
+            tags: Some(tags.into()),
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-bucket" {"#));
         assert!(hcl.contains(r#"bucket = "my-bucket""#));
-        assert!(hcl.contains(r#"bucket_prefix = "prefix-""#));
+        assert!(hcl.contains(r#"acl = "bucket-owner-full-control""#));
         assert!(hcl.contains(r#"force_destroy = true"#));
         assert!(hcl.contains(r#"object_lock_enabled = false"#));
         assert!(hcl.contains(r#"tags = {"#));
@@ -301,10 +472,15 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
-        let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"resource "aws_s3_bucket" {"#));
+        assert!(matches!(
+            Block::try_from(bucket),
+            Err(Error::MissingArgument {
+                resource: "aws_s3_bucket",
+                argument: "bucket"
+            })
+        ));
     }
 
     #[test]
@@ -315,9 +491,10 @@ mod tests {
             acl: None,
             force_destroy: None,
             object_lock_enabled: None,
-            tags: Some(HashMap::new()),
+            tags: Some(HashMap::new().into()),
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"tags = {}"#));
     }
@@ -334,9 +511,10 @@ mod tests {
             acl: None,
             force_destroy: None,
             object_lock_enabled: None,
-            tags: Some(tags),
+            tags: Some(tags.into()),
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"tags = {"#));
         assert!(hcl.contains(r#""Environment" = "Production""#));
@@ -353,8 +531,9 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-very-long-bucket-name-that-is-exactly-63-characters-long" {"#));
         assert!(hcl
@@ -364,17 +543,65 @@ mod tests {
     #[test]
     fn test_bucket_with_special_characters_in_name() {
         let bucket = Bucket {
-            name: Some("my-bucket-with-special-chars-_.-".to_string()),
+            name: Some("my-bucket.with-dots".to_string()),
             prefix: None,
             acl: None,
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        assert_eq!(
+            bucket.arn().to_string(),
+            "aws_s3_bucket.my-bucket_with-dots.arn"
+        );
+
+        let block = Block::try_from(bucket.clone()).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-bucket_with-dots" {"#));
+        assert!(hcl.contains(r#"bucket = "my-bucket.with-dots""#));
+
+        let block = Block::try_from(Bucket {
+            name: Some("2024.logs".to_string()),
+            ..bucket
+        })
+        .unwrap();
         let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"resource "aws_s3_bucket" "my-bucket-with-special-chars-_.-" {"#));
-        assert!(hcl.contains(r#"bucket = "my-bucket-with-special-chars-_.-""#));
+        assert!(hcl.contains(r#"resource "aws_s3_bucket" "_2024_logs" {"#));
+        assert!(hcl.contains(r#"bucket = "2024.logs""#));
+    }
+
+    #[test]
+    fn test_bucket_with_invalid_names() {
+        for name in [
+            "ab",
+            "my-bucket-with-special-chars-_.-",
+            "-my-bucket",
+            "my-bucket.",
+            "my..bucket",
+            "192.168.5.4",
+        ] {
+            let bucket = Bucket {
+                name: Some(name.to_string()),
+                prefix: None,
+                acl: None,
+                force_destroy: None,
+                object_lock_enabled: None,
+                tags: None,
+                meta: MetaArguments::default(),
+            };
+            assert!(
+                matches!(
+                    Block::try_from(bucket),
+                    Err(Error::InvalidArgument {
+                        argument: "bucket",
+                        ..
+                    })
+                ),
+                "{} should be rejected",
+                name
+            );
+        }
     }
 
     #[test]
@@ -383,11 +610,12 @@ mod tests {
             name: Some("my-bucket".to_string()),
             prefix: None,
             acl: None,
-            force_destroy: Some(false),
+            force_destroy: Some(false.into()),
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"force_destroy = false"#));
     }
@@ -399,10 +627,11 @@ mod tests {
             prefix: None,
             acl: None,
             force_destroy: None,
-            object_lock_enabled: Some(false),
+            object_lock_enabled: Some(false.into()),
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"object_lock_enabled = false"#));
     }
@@ -416,11 +645,16 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
-        let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"bucket = "my-bucket""#));
-        assert!(hcl.contains(r#"bucket_prefix = "prefix-""#));
+        assert!(matches!(
+            Block::try_from(bucket),
+            Err(Error::ConflictingArguments {
+                first: "bucket",
+                second: "bucket_prefix",
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -436,9 +670,10 @@ mod tests {
             acl: None,
             force_destroy: None,
             object_lock_enabled: None,
-            tags: Some(tags),
+            tags: Some(tags.into()),
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#""Description" = "This is a \"quoted\" value""#));
     }
@@ -452,15 +687,9 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
-        let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"resource "aws_s3_bucket" {"#));
-        assert!(!hcl.contains("bucket ="));
-        assert!(!hcl.contains("bucket_prefix ="));
-        assert!(!hcl.contains("force_destroy ="));
-        assert!(!hcl.contains("object_lock_enabled ="));
-        assert!(!hcl.contains("tags ="));
+        assert!(Block::try_from(bucket).is_err());
     }
 
     #[test]
@@ -472,11 +701,15 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
-        let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"resource "aws_s3_bucket" "" {"#));
-        assert!(hcl.contains(r#"bucket = """#));
+        assert!(matches!(
+            Block::try_from(bucket),
+            Err(Error::InvalidArgument {
+                argument: "bucket",
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -488,10 +721,15 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
-        let hcl = hcl::to_string(&block).unwrap();
-        assert!(hcl.contains(r#"bucket_prefix = """#));
+        assert!(matches!(
+            Block::try_from(bucket),
+            Err(Error::InvalidArgument {
+                argument: "bucket_prefix",
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -499,12 +737,13 @@ mod tests {
         let bucket = Bucket {
             name: Some("my-bucket".to_string()),
             prefix: None,
-            acl: Some(ACLOptions::PublicRead),
+            acl: Some(ACLOptions::PublicRead.into()),
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"acl = "public-read""#));
     }
@@ -514,12 +753,13 @@ mod tests {
         let bucket = Bucket {
             name: Some("my-bucket".to_string()),
             prefix: None,
-            acl: Some(ACLOptions::Private),
+            acl: Some(ACLOptions::Private.into()),
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
         assert!(hcl.contains(r#"acl = "private""#));
     }
@@ -533,9 +773,76 @@ mod tests {
             force_destroy: None,
             object_lock_enabled: None,
             tags: None,
+            meta: MetaArguments::default(),
+        };
+        let block = Block::try_from(bucket).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(r#"acl = "private""#));
+    }
+
+    #[test]
+    fn test_bucket_with_uppercase_name() {
+        let bucket = Bucket {
+            name: Some("My-Bucket".to_string()),
+            prefix: None,
+            acl: None,
+            force_destroy: None,
+            object_lock_enabled: None,
+            tags: None,
+            meta: MetaArguments::default(),
+        };
+        assert!(Block::try_from(bucket).is_err());
+    }
+
+    #[test]
+    fn test_bucket_acl_with_acl_and_access_control_policy() {
+        let bucket_acl = BucketACL {
+            acl: Some(ACLOptions::Private.into()),
+            access_control_policy: Some(AccessControlPolicy::ACL),
+            bucket: "my-bucket".to_string(),
+            expected_bucket_owner: None,
+            meta: MetaArguments::default(),
+        };
+        assert!(matches!(
+            Block::try_from(bucket_acl),
+            Err(Error::ConflictingArguments {
+                resource: "aws_s3_bucket_acl",
+                first: "acl",
+                second: "access_control_policy"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bucket_acl_without_acl_or_access_control_policy() {
+        let bucket_acl = BucketACL {
+            acl: None,
+            access_control_policy: None,
+            bucket: "my-bucket".to_string(),
+            expected_bucket_owner: None,
+            meta: MetaArguments::default(),
+        };
+        assert!(matches!(
+            Block::try_from(bucket_acl),
+            Err(Error::MissingArgument {
+                resource: "aws_s3_bucket_acl",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bucket_acl_to_hcl() {
+        let bucket_acl = BucketACL {
+            acl: Some(ACLOptions::Private.into()),
+            access_control_policy: None,
+            bucket: "my-bucket".to_string(),
+            expected_bucket_owner: None,
+            meta: MetaArguments::default(),
         };
-        let block: Block = bucket.into();
+        let block = Block::try_from(bucket_acl).unwrap();
         let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(r#"resource "aws_s3_bucket_acl" "my-bucket" {"#));
         assert!(hcl.contains(r#"acl = "private""#));
     }
 }
@@ -0,0 +1,171 @@
+use hcl::{Block, Expression};
+
+/// Where Terraform stores state, rendered as a `backend` block inside `terraform {}`.
+#[derive(Debug, Clone)]
+pub enum Backend {
+    Local(Local),
+    Http(Http),
+    #[cfg(feature = "aws")]
+    S3(crate::aws::backend::S3),
+}
+
+impl From<Backend> for Block {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Local(local) => local.into(),
+            Backend::Http(http) => http.into(),
+            #[cfg(feature = "aws")]
+            Backend::S3(s3) => s3.into(),
+        }
+    }
+}
+
+impl From<Local> for Backend {
+    fn from(local: Local) -> Self {
+        Backend::Local(local)
+    }
+}
+
+impl From<Http> for Backend {
+    fn from(http: Http) -> Self {
+        Backend::Http(http)
+    }
+}
+
+/// Stores state on the local filesystem.
+#[derive(Debug, Clone, Default)]
+pub struct Local {
+    /// Path to the state file. Defaults to `terraform.tfstate`.
+    pub path: Option<String>,
+
+    /// Path to non-default workspaces.
+    pub workspace_dir: Option<String>,
+}
+
+impl From<Local> for Block {
+    fn from(local: Local) -> Self {
+        let mut block = Block::builder("backend").add_label("local");
+
+        if let Some(path) = local.path {
+            block = block.add_attribute(("path", Expression::String(path)));
+        }
+
+        if let Some(workspace_dir) = local.workspace_dir {
+            block = block.add_attribute(("workspace_dir", Expression::String(workspace_dir)));
+        }
+
+        block.build()
+    }
+}
+
+/// Stores state using a simple REST client.
+#[derive(Debug, Clone, Default)]
+pub struct Http {
+    /// The address of the REST endpoint.
+    pub address: String,
+
+    /// HTTP method to use when updating state. Defaults to `POST`.
+    pub update_method: Option<String>,
+
+    /// The address of the lock REST endpoint. Locking is disabled when unset.
+    pub lock_address: Option<String>,
+
+    /// The HTTP method to use when locking. Defaults to `LOCK`.
+    pub lock_method: Option<String>,
+
+    /// The address of the unlock REST endpoint.
+    pub unlock_address: Option<String>,
+
+    /// The HTTP method to use when unlocking. Defaults to `UNLOCK`.
+    pub unlock_method: Option<String>,
+
+    /// The username for HTTP basic authentication.
+    pub username: Option<String>,
+
+    /// The password for HTTP basic authentication.
+    pub password: Option<String>,
+
+    /// Whether to skip TLS verification. Defaults to `false`.
+    pub skip_cert_verification: Option<bool>,
+}
+
+impl From<Http> for Block {
+    fn from(http: Http) -> Self {
+        let mut block = Block::builder("backend")
+            .add_label("http")
+            .add_attribute(("address", Expression::String(http.address)));
+
+        if let Some(update_method) = http.update_method {
+            block = block.add_attribute(("update_method", Expression::String(update_method)));
+        }
+
+        if let Some(lock_address) = http.lock_address {
+            block = block.add_attribute(("lock_address", Expression::String(lock_address)));
+        }
+
+        if let Some(lock_method) = http.lock_method {
+            block = block.add_attribute(("lock_method", Expression::String(lock_method)));
+        }
+
+        if let Some(unlock_address) = http.unlock_address {
+            block = block.add_attribute(("unlock_address", Expression::String(unlock_address)));
+        }
+
+        if let Some(unlock_method) = http.unlock_method {
+            block = block.add_attribute(("unlock_method", Expression::String(unlock_method)));
+        }
+
+        if let Some(username) = http.username {
+            block = block.add_attribute(("username", Expression::String(username)));
+        }
+
+        if let Some(password) = http.password {
+            block = block.add_attribute(("password", Expression::String(password)));
+        }
+
+        if let Some(skip_cert_verification) = http.skip_cert_verification {
+            block = block.add_attribute((
+                "skip_cert_verification",
+                Expression::Bool(skip_cert_verification),
+            ));
+        }
+
+        block.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_backend_to_hcl() {
+        let block: Block = Backend::from(Local {
+            path: Some("state/terraform.tfstate".to_string()),
+            workspace_dir: None,
+        })
+        .into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"backend "local" {"#));
+        assert!(hcl.contains(r#"path = "state/terraform.tfstate""#));
+        assert!(!hcl.contains("workspace_dir"));
+    }
+
+    #[test]
+    fn test_http_backend_to_hcl() {
+        let block: Block = Backend::from(Http {
+            address: "https://state.example.com/main".to_string(),
+            lock_address: Some("https://state.example.com/main/lock".to_string()),
+            skip_cert_verification: Some(false),
+            ..Default::default()
+        })
+        .into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"backend "http" {"#));
+        assert!(hcl.contains(r#"address = "https://state.example.com/main""#));
+        assert!(hcl.contains(r#"lock_address = "https://state.example.com/main/lock""#));
+        assert!(hcl.contains("skip_cert_verification = false"));
+    }
+}
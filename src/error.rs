@@ -0,0 +1,134 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned while building or writing a configuration.
+#[derive(Debug)]
+pub enum Error {
+    /// A required argument was not set.
+    MissingArgument {
+        resource: &'static str,
+        argument: &'static str,
+    },
+    /// Two mutually exclusive arguments were both set.
+    ConflictingArguments {
+        resource: &'static str,
+        first: &'static str,
+        second: &'static str,
+    },
+    /// An argument was set to a value the resource does not accept.
+    InvalidArgument {
+        resource: &'static str,
+        argument: &'static str,
+        reason: String,
+    },
+    /// A CIDR prefix length is larger than the address length.
+    InvalidPrefixLength { prefix_length: u8, max: u8 },
+    /// Two blocks in the same configuration share an address, such as `aws_vpc.main`.
+    DuplicateBlock { address: String },
+    /// Resources that are each valid but inconsistent with one another, such as
+    /// overlapping subnets. Holds every violation found, not just the first.
+    Violations(Vec<Violation>),
+    /// The configuration could not be serialized to HCL.
+    Hcl(hcl::Error),
+    /// The configuration could not be written to disk.
+    Io(io::Error),
+    /// A state file, such as IPAM allocations, could not be read, written or parsed.
+    StateFile { path: PathBuf, reason: String },
+}
+
+impl Error {
+    /// Shorthand for [`Error::InvalidArgument`].
+    pub fn invalid<R: Into<String>>(
+        resource: &'static str,
+        argument: &'static str,
+        reason: R,
+    ) -> Self {
+        Error::InvalidArgument {
+            resource,
+            argument,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A problem spanning several resources, found by a cross-resource check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Addresses of the resources involved, such as `aws_subnet.public_a`.
+    pub resources: Vec<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.resources.join(", "), self.reason)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingArgument { resource, argument } => {
+                write!(f, "{}: missing required argument `{}`", resource, argument)
+            }
+            Error::ConflictingArguments {
+                resource,
+                first,
+                second,
+            } => write!(f, "{}: `{}` conflicts with `{}`", resource, first, second),
+            Error::InvalidArgument {
+                resource,
+                argument,
+                reason,
+            } => write!(f, "{}: invalid `{}`: {}", resource, argument, reason),
+            Error::InvalidPrefixLength { prefix_length, max } => write!(
+                f,
+                "prefix length {} must be between 0 and {}",
+                prefix_length, max
+            ),
+            Error::DuplicateBlock { address } => write!(f, "duplicate block `{}`", address),
+            Error::Violations(violations) => {
+                write!(f, "{} violation(s):", violations.len())?;
+                for violation in violations {
+                    write!(f, "\n  - {}", violation)?;
+                }
+                Ok(())
+            }
+            Error::Hcl(err) => write!(f, "failed to serialize HCL: {}", err),
+            Error::Io(err) => write!(f, "failed to write configuration: {}", err),
+            Error::StateFile { path, reason } => {
+                write!(f, "state file `{}`: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Hcl(err) => Some(err),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl From<hcl::Error> for Error {
+    fn from(err: hcl::Error) -> Self {
+        Error::Hcl(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
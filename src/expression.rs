@@ -0,0 +1,118 @@
+use crate::error::Error;
+use crate::reference::Reference;
+use hcl::{Expression, Identifier, ObjectKey};
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+
+/// Converts a literal into the HCL expression used for an attribute value.
+///
+/// This plays the role of `Into<Expression>` for types that `hcl` does not know about,
+/// such as CIDR blocks, regions and tag maps.
+pub trait IntoExpression {
+    fn into_expression(self) -> Expression;
+}
+
+impl IntoExpression for Expression {
+    fn into_expression(self) -> Expression {
+        self
+    }
+}
+
+impl IntoExpression for Reference {
+    fn into_expression(self) -> Expression {
+        self.into()
+    }
+}
+
+macro_rules! impl_into_expression {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoExpression for $ty {
+                fn into_expression(self) -> Expression {
+                    Expression::from(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_expression!(String, bool, u8, u16, u32, u64, i32, i64, f64);
+
+/// IP addresses render as strings, such as `"10.0.0.2"`.
+impl IntoExpression for IpAddr {
+    fn into_expression(self) -> Expression {
+        Expression::String(self.to_string())
+    }
+}
+
+impl<T: IntoExpression> IntoExpression for Vec<T> {
+    fn into_expression(self) -> Expression {
+        Expression::Array(self.into_iter().map(T::into_expression).collect())
+    }
+}
+
+/// Maps render with quoted keys, matching how tags have always been written.
+impl<V: IntoExpression> IntoExpression for HashMap<String, V> {
+    fn into_expression(self) -> Expression {
+        Expression::Object(
+            self.into_iter()
+                .map(|(k, v)| (ObjectKey::from(k), v.into_expression()))
+                .collect(),
+        )
+    }
+}
+
+/// Ordered maps render with identifier keys, as used for `object({...})`-style values.
+impl<V: IntoExpression> IntoExpression for BTreeMap<String, V> {
+    fn into_expression(self) -> Expression {
+        Expression::Object(
+            self.into_iter()
+                .map(|(k, v)| {
+                    (
+                        ObjectKey::from(Identifier::sanitized(k)),
+                        v.into_expression(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Parses a single HCL expression, such as `merge(var.tags, { Team = "data" })`.
+pub fn parse(input: &str) -> Result<Expression, Error> {
+    let body = hcl::parse(&format!("value = {}", input))?;
+    let attribute = body
+        .into_attributes()
+        .next()
+        .ok_or_else(|| Error::invalid("expression", "input", "expected a single expression"))?;
+
+    Ok(attribute.expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expression() {
+        let expr = parse("length(var.name) > 0").unwrap();
+        assert_eq!(
+            hcl::format::to_string(&expr).unwrap(),
+            "length(var.name) > 0"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_expression() {
+        assert!(matches!(parse("length(var.name"), Err(Error::Hcl(_))));
+    }
+
+    #[test]
+    fn test_vec_into_expression() {
+        let expr = vec!["a".to_string(), "b".to_string()].into_expression();
+        assert_eq!(
+            expr,
+            Expression::Array(vec![Expression::from("a"), Expression::from("b")])
+        );
+    }
+}
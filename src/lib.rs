@@ -1,2 +1,36 @@
+// Lets code generated by `nexus7-derive` refer to `::nexus7` from inside this crate.
+extern crate self as nexus7;
+
 #[cfg(feature = "aws")]
 pub mod aws;
+pub mod backend;
+pub mod error;
+pub mod expression;
+pub mod locals;
+pub mod meta;
+pub mod output;
+pub mod provider;
+pub mod reference;
+pub mod stack;
+pub mod terraform;
+pub mod value;
+pub mod variable;
+
+pub use backend::Backend;
+pub use error::{Error, Violation};
+pub use expression::IntoExpression;
+pub use locals::Locals;
+pub use meta::MetaArguments;
+pub use nexus7_derive::HclResource;
+pub use output::Output;
+pub use provider::{Provider, ProviderRef, ProviderRequirement};
+pub use reference::{DataSource, Reference, Resource};
+pub use stack::Stack;
+pub use terraform::TerraformSettings;
+pub use value::Value;
+pub use variable::Variable;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use hcl;
+}
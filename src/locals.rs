@@ -0,0 +1,107 @@
+use crate::error::Error;
+use crate::expression::IntoExpression;
+use crate::reference::Reference;
+use hcl::{Block, Expression, Identifier};
+
+/// Builds a `locals` block of named values that are evaluated by Terraform.
+///
+/// Each value added returns a `local.<name>` reference that can be used as any attribute
+/// value.
+#[derive(Debug, Clone, Default)]
+pub struct Locals {
+    values: Vec<(String, Expression)>,
+}
+
+impl Locals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a local value and returns a reference to it.
+    pub fn add<V: IntoExpression>(&mut self, name: &str, value: V) -> Reference {
+        self.values
+            .push((name.to_string(), value.into_expression()));
+        Reference::local(name)
+    }
+
+    /// Whether any values have been added.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Checks that every name is a valid identifier and is only defined once.
+    pub fn validate(&self) -> Result<(), Error> {
+        for (i, (name, _)) in self.values.iter().enumerate() {
+            if Identifier::new(name.as_str()).is_err() {
+                return Err(Error::invalid(
+                    "locals",
+                    "name",
+                    format!("`{}` is not a valid identifier", name),
+                ));
+            }
+
+            if self.values[..i].iter().any(|(other, _)| other == name) {
+                return Err(Error::DuplicateBlock {
+                    address: format!("local.{}", name),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Locals> for Block {
+    type Error = Error;
+
+    fn try_from(locals: Locals) -> Result<Self, Self::Error> {
+        locals.validate()?;
+
+        let block = locals
+            .values
+            .into_iter()
+            .fold(Block::builder("locals"), |block, (name, value)| {
+                block.add_attribute((name, value))
+            });
+
+        Ok(block.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression;
+
+    #[test]
+    fn test_locals_to_hcl() {
+        let mut locals = Locals::new();
+        let tags = locals.add(
+            "common_tags",
+            expression::parse(r#"merge(var.tags, { Team = "data" })"#).unwrap(),
+        );
+        let name = locals.add("cluster_name", "analytics".to_string());
+
+        assert_eq!(tags.to_string(), "local.common_tags");
+        assert_eq!(name.to_string(), "local.cluster_name");
+
+        let block = Block::try_from(locals).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains("locals {"));
+        assert!(hcl.contains("common_tags = merge(var.tags, {"));
+        assert!(hcl.contains(r#"cluster_name = "analytics""#));
+    }
+
+    #[test]
+    fn test_locals_rejects_duplicate_names() {
+        let mut locals = Locals::new();
+        locals.add("name", "a".to_string());
+        locals.add("name", "b".to_string());
+
+        assert!(matches!(
+            Block::try_from(locals),
+            Err(Error::DuplicateBlock { address }) if address == "local.name"
+        ));
+    }
+}
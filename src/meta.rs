@@ -0,0 +1,247 @@
+use crate::error::Error;
+use crate::provider::ProviderRef;
+use crate::reference::Reference;
+use crate::value::Value;
+use hcl::structure::BlockBuilder;
+use hcl::{Block, Expression};
+
+/// Meta-arguments accepted by every resource, rendered after the resource's own arguments.
+#[derive(Debug, Clone, Default)]
+pub struct MetaArguments {
+    /// Number of instances to create. Conflicts with `for_each`.
+    pub count: Option<Value<u32>>,
+
+    /// Map or set of strings to create one instance per element of. Conflicts with `count`.
+    pub for_each: Option<Expression>,
+
+    /// Resources that must be applied before this one, for dependencies Terraform cannot
+    /// infer from references.
+    pub depends_on: Vec<Reference>,
+
+    /// Non-default provider configuration, such as `aws.west`.
+    pub provider: Option<ProviderRef>,
+
+    /// Customizes how the resource is created, updated and destroyed.
+    pub lifecycle: Option<Lifecycle>,
+}
+
+/// Attributes whose changes Terraform should ignore when planning updates.
+#[derive(Debug, Clone)]
+pub enum IgnoreChanges {
+    /// Ignore changes to every attribute.
+    All,
+    /// Ignore changes to the named attributes.
+    Attributes(Vec<String>),
+}
+
+/// Settings for the nested `lifecycle` block.
+#[derive(Debug, Clone, Default)]
+pub struct Lifecycle {
+    /// Creates the replacement object before destroying the current one.
+    pub create_before_destroy: Option<bool>,
+
+    /// Rejects any plan that would destroy the resource.
+    pub prevent_destroy: Option<bool>,
+
+    /// Attributes whose changes are ignored.
+    pub ignore_changes: Option<IgnoreChanges>,
+
+    /// Resources or attributes whose changes force this resource to be replaced.
+    pub replace_triggered_by: Vec<Reference>,
+}
+
+impl MetaArguments {
+    /// Checks that `count` and `for_each` are not both set on `resource`.
+    pub fn validate(&self, resource: &'static str) -> Result<(), Error> {
+        if self.count.is_some() && self.for_each.is_some() {
+            return Err(Error::ConflictingArguments {
+                resource,
+                first: "count",
+                second: "for_each",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Adds the meta-arguments to a resource block. Called last by every resource's block
+    /// conversion.
+    pub fn apply(self, mut block: BlockBuilder) -> BlockBuilder {
+        if let Some(count) = self.count {
+            block = block.add_attribute(("count", count));
+        }
+
+        if let Some(for_each) = self.for_each {
+            block = block.add_attribute(("for_each", for_each));
+        }
+
+        if let Some(provider) = self.provider {
+            block = block.add_attribute(("provider", Reference::from(provider)));
+        }
+
+        if !self.depends_on.is_empty() {
+            block = block.add_attribute((
+                "depends_on",
+                Expression::Array(self.depends_on.into_iter().map(Expression::from).collect()),
+            ));
+        }
+
+        if let Some(lifecycle) = self.lifecycle {
+            block = block.add_block(lifecycle);
+        }
+
+        block
+    }
+}
+
+impl From<Lifecycle> for Block {
+    fn from(lifecycle: Lifecycle) -> Self {
+        let mut block = Block::builder("lifecycle");
+
+        if let Some(create_before_destroy) = lifecycle.create_before_destroy {
+            block = block.add_attribute((
+                "create_before_destroy",
+                Expression::Bool(create_before_destroy),
+            ));
+        }
+
+        if let Some(prevent_destroy) = lifecycle.prevent_destroy {
+            block = block.add_attribute(("prevent_destroy", Expression::Bool(prevent_destroy)));
+        }
+
+        match lifecycle.ignore_changes {
+            Some(IgnoreChanges::All) => {
+                block = block.add_attribute((
+                    "ignore_changes",
+                    Expression::Variable(hcl::expr::Variable::unchecked("all")),
+                ));
+            }
+            Some(IgnoreChanges::Attributes(attributes)) => {
+                block = block.add_attribute((
+                    "ignore_changes",
+                    Expression::Array(
+                        attributes
+                            .into_iter()
+                            .map(|a| Expression::Variable(hcl::expr::Variable::sanitized(&a)))
+                            .collect(),
+                    ),
+                ));
+            }
+            None => {}
+        }
+
+        if !lifecycle.replace_triggered_by.is_empty() {
+            block = block.add_attribute((
+                "replace_triggered_by",
+                Expression::Array(
+                    lifecycle
+                        .replace_triggered_by
+                        .into_iter()
+                        .map(Expression::from)
+                        .collect(),
+                ),
+            ));
+        }
+
+        block.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+
+    struct West;
+
+    impl Provider for West {
+        const NAME: &'static str = "aws";
+        const SOURCE: &'static str = "hashicorp/aws";
+        const VERSION: &'static str = "~> 5.0";
+
+        fn alias(&self) -> Option<&str> {
+            Some("west")
+        }
+    }
+
+    fn render(meta: MetaArguments) -> String {
+        let block = meta
+            .apply(
+                Block::builder("resource")
+                    .add_label("aws_s3_bucket")
+                    .add_label("state"),
+            )
+            .build();
+        hcl::to_string(&block).unwrap()
+    }
+
+    #[test]
+    fn test_meta_arguments_to_hcl() {
+        let hcl = render(MetaArguments {
+            count: Some(Reference::variable("bucket_count").into()),
+            provider: Some(West.handle()),
+            depends_on: vec![Reference::resource("aws_internet_gateway", "main")],
+            lifecycle: Some(Lifecycle {
+                prevent_destroy: Some(true),
+                create_before_destroy: Some(true),
+                ignore_changes: Some(IgnoreChanges::Attributes(vec!["tags".to_string()])),
+                replace_triggered_by: vec![Reference::resource("aws_vpc", "main").attr("id")],
+            }),
+            ..Default::default()
+        });
+
+        assert!(hcl.contains("count = var.bucket_count"));
+        assert!(hcl.contains("provider = aws.west"));
+        assert!(hcl.contains(
+            r#"depends_on = [
+    aws_internet_gateway.main
+  ]"#
+        ));
+        assert!(hcl.contains("lifecycle {"));
+        assert!(hcl.contains("prevent_destroy = true"));
+        assert!(hcl.contains("create_before_destroy = true"));
+        assert!(hcl.contains(
+            r#"ignore_changes = [
+      tags
+    ]"#
+        ));
+        assert!(hcl.contains("aws_vpc.main.id"));
+    }
+
+    #[test]
+    fn test_ignore_all_changes() {
+        let hcl = render(MetaArguments {
+            lifecycle: Some(Lifecycle {
+                ignore_changes: Some(IgnoreChanges::All),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        assert!(hcl.contains("ignore_changes = all"));
+    }
+
+    #[test]
+    fn test_count_conflicts_with_for_each() {
+        let meta = MetaArguments {
+            count: Some(2.into()),
+            for_each: Some(Reference::variable("subnets").into()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            meta.validate("aws_subnet"),
+            Err(Error::ConflictingArguments {
+                first: "count",
+                second: "for_each",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_empty_meta_arguments_render_nothing() {
+        let hcl = render(MetaArguments::default());
+        assert_eq!(hcl.trim(), r#"resource "aws_s3_bucket" "state" {}"#);
+    }
+}
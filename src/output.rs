@@ -0,0 +1,128 @@
+use crate::error::Error;
+use crate::reference::Reference;
+use hcl::{Block, Expression, Identifier};
+
+/// Represents a Terraform output value.
+#[derive(Debug, Clone)]
+pub struct Output {
+    /// Name of the output, as shown by `terraform output`.
+    pub name: String,
+
+    /// Attribute to expose, such as `aws_vpc.main.id`.
+    pub value: Reference,
+
+    /// Documentation for the output.
+    pub description: Option<String>,
+
+    /// Hides the value in plan and apply output.
+    pub sensitive: Option<bool>,
+
+    /// Resources that must be applied before the value is considered available.
+    pub depends_on: Vec<Reference>,
+}
+
+impl Output {
+    /// An output exposing `value` under `name`, with no other arguments set.
+    pub fn new(name: impl Into<String>, value: Reference) -> Self {
+        Output {
+            name: name.into(),
+            value,
+            description: None,
+            sensitive: None,
+            depends_on: vec![],
+        }
+    }
+
+    /// Checks that the output name is a valid identifier.
+    pub fn validate(&self) -> Result<(), Error> {
+        if Identifier::new(self.name.as_str()).is_err() {
+            return Err(Error::invalid(
+                "output",
+                "name",
+                format!("`{}` is not a valid identifier", self.name),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Output> for Block {
+    type Error = Error;
+
+    fn try_from(output: Output) -> Result<Self, Self::Error> {
+        output.validate()?;
+
+        let mut block = Block::builder("output")
+            .add_label(&output.name)
+            .add_attribute(("value", output.value));
+
+        if let Some(description) = output.description {
+            block = block.add_attribute(("description", Expression::String(description)));
+        }
+
+        if let Some(sensitive) = output.sensitive {
+            block = block.add_attribute(("sensitive", Expression::Bool(sensitive)));
+        }
+
+        if !output.depends_on.is_empty() {
+            block = block.add_attribute((
+                "depends_on",
+                Expression::Array(
+                    output
+                        .depends_on
+                        .into_iter()
+                        .map(Expression::from)
+                        .collect(),
+                ),
+            ));
+        }
+
+        Ok(block.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_to_hcl() {
+        let output = Output {
+            description: Some("Password for the admin user".to_string()),
+            sensitive: Some(true),
+            depends_on: vec![Reference::resource("aws_db_instance", "main")],
+            ..Output::new(
+                "admin_password",
+                Reference::resource("aws_db_instance", "main").attr("password"),
+            )
+        };
+
+        let block = Block::try_from(output).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"output "admin_password" {"#));
+        assert!(hcl.contains("value = aws_db_instance.main.password"));
+        assert!(hcl.contains(r#"description = "Password for the admin user""#));
+        assert!(hcl.contains("sensitive = true"));
+        assert!(hcl.contains(
+            r#"depends_on = [
+    aws_db_instance.main
+  ]"#
+        ));
+    }
+
+    #[test]
+    fn test_output_invalid_name() {
+        let output = Output::new("vpc id", Reference::resource("aws_vpc", "main").attr("id"));
+
+        assert!(matches!(
+            Block::try_from(output),
+            Err(Error::InvalidArgument {
+                resource: "output",
+                argument: "name",
+                ..
+            })
+        ));
+    }
+}
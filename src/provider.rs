@@ -0,0 +1,70 @@
+use crate::reference::Reference;
+
+/// Source address and version constraint of a provider, as listed in `required_providers`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderRequirement {
+    /// Registry source address, such as `hashicorp/aws`.
+    pub source: String,
+    /// Version constraint, such as `~> 5.0`.
+    pub version: Option<String>,
+}
+
+/// Implemented by every type that renders as a `provider` block.
+pub trait Provider {
+    /// Local name of the provider, such as `aws`.
+    const NAME: &'static str;
+
+    /// Registry source address of the provider.
+    const SOURCE: &'static str;
+
+    /// Version constraint the crate's resources are written against.
+    const VERSION: &'static str;
+
+    /// Alias of this configuration, or `None` for the provider's default configuration.
+    fn alias(&self) -> Option<&str> {
+        None
+    }
+
+    /// Handle for selecting this configuration through a resource's `provider`
+    /// meta-argument.
+    fn handle(&self) -> ProviderRef {
+        ProviderRef {
+            name: Self::NAME,
+            alias: self.alias().map(str::to_string),
+        }
+    }
+
+    /// The requirement this provider adds to the `terraform` settings block.
+    fn requirement(&self) -> ProviderRequirement {
+        ProviderRequirement {
+            source: Self::SOURCE.to_string(),
+            version: Some(Self::VERSION.to_string()),
+        }
+    }
+}
+
+/// Selects a provider configuration, rendered as `aws` or `aws.west`. Obtained from
+/// [`Provider::handle`], so it always names a provider the crate knows about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderRef {
+    name: &'static str,
+    alias: Option<String>,
+}
+
+impl ProviderRef {
+    /// Local name of the provider, such as `aws`.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Alias of the selected configuration.
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+}
+
+impl From<ProviderRef> for Reference {
+    fn from(handle: ProviderRef) -> Self {
+        Reference::provider(handle.name, handle.alias.as_deref())
+    }
+}
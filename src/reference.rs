@@ -0,0 +1,188 @@
+use hcl::expr::{Traversal, TraversalOperator, Variable};
+use hcl::{Expression, Identifier};
+use std::fmt;
+
+/// A reference to a block or one of its exported attributes, rendered as an HCL traversal
+/// such as `aws_vpc.main.id` rather than as a quoted `"${...}"` string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    root: String,
+    operators: Vec<TraversalOperator>,
+}
+
+impl Reference {
+    /// Reference to a managed resource, e.g. `aws_vpc.main`. `name` is sanitized into an
+    /// identifier like the block label, so `logs.example.com` becomes `logs_example_com`.
+    pub fn resource(kind: &str, name: &str) -> Self {
+        Reference {
+            root: kind.to_string(),
+            operators: vec![TraversalOperator::GetAttr(Identifier::sanitized(name))],
+        }
+    }
+
+    /// Reference to a data source, e.g. `data.aws_vpc.main`, with `name` sanitized like the
+    /// block label.
+    pub fn data(kind: &str, name: &str) -> Self {
+        Reference {
+            root: "data".to_string(),
+            operators: vec![
+                TraversalOperator::GetAttr(Identifier::from(kind)),
+                TraversalOperator::GetAttr(Identifier::sanitized(name)),
+            ],
+        }
+    }
+
+    /// Reference to an input variable, e.g. `var.cidr_block`.
+    pub fn variable(name: &str) -> Self {
+        Reference {
+            root: "var".to_string(),
+            operators: vec![TraversalOperator::GetAttr(Identifier::from(name))],
+        }
+    }
+
+    /// Reference to a local value, e.g. `local.common_tags`.
+    pub fn local(name: &str) -> Self {
+        Reference {
+            root: "local".to_string(),
+            operators: vec![TraversalOperator::GetAttr(Identifier::from(name))],
+        }
+    }
+
+    /// Reference to a provider configuration, e.g. `aws` or `aws.west`.
+    pub fn provider(name: &str, alias: Option<&str>) -> Self {
+        Reference {
+            root: name.to_string(),
+            operators: alias
+                .map(|alias| TraversalOperator::GetAttr(Identifier::from(alias)))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Appends an attribute access, e.g. `.id`.
+    pub fn attr(mut self, name: &str) -> Self {
+        self.operators
+            .push(TraversalOperator::GetAttr(Identifier::from(name)));
+        self
+    }
+
+    /// Appends an index access, e.g. `[0]`.
+    pub fn index<T: Into<Expression>>(mut self, index: T) -> Self {
+        self.operators.push(TraversalOperator::Index(index.into()));
+        self
+    }
+}
+
+impl From<Reference> for Traversal {
+    fn from(reference: Reference) -> Self {
+        Traversal::new(Variable::sanitized(reference.root), reference.operators)
+    }
+}
+
+impl From<Reference> for Expression {
+    fn from(reference: Reference) -> Self {
+        Expression::from(Traversal::from(reference))
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expr = Expression::from(self.clone());
+        let rendered = hcl::format::to_string(&expr).map_err(|_| fmt::Error)?;
+        f.write_str(&rendered)
+    }
+}
+
+/// Implemented by every type that renders as a `resource` block.
+pub trait Resource {
+    /// Terraform resource type, such as `aws_vpc`.
+    const TYPE: &'static str;
+
+    /// Label of the resource block.
+    fn name(&self) -> &str;
+
+    /// Reference to the resource itself, as used in `depends_on`.
+    fn reference(&self) -> Reference {
+        Reference::resource(Self::TYPE, self.name())
+    }
+
+    /// Reference to an exported attribute of the resource.
+    fn attr(&self, name: &str) -> Reference {
+        self.reference().attr(name)
+    }
+}
+
+/// Implemented by every type that renders as a `data` block.
+pub trait DataSource {
+    /// Terraform data source type, such as `aws_vpc`.
+    const TYPE: &'static str;
+
+    /// Label of the data block.
+    fn name(&self) -> &str;
+
+    /// Reference to the data source itself.
+    fn reference(&self) -> Reference {
+        Reference::data(Self::TYPE, self.name())
+    }
+
+    /// Reference to an exported attribute of the data source.
+    fn attr(&self, name: &str) -> Reference {
+        self.reference().attr(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_reference() {
+        let reference = Reference::resource("aws_vpc", "main").attr("id");
+        assert_eq!(reference.to_string(), "aws_vpc.main.id");
+    }
+
+    #[test]
+    fn test_data_reference() {
+        let reference = Reference::data("aws_vpc", "main").attr("cidr_block");
+        assert_eq!(reference.to_string(), "data.aws_vpc.main.cidr_block");
+    }
+
+    #[test]
+    fn test_nested_reference() {
+        let reference = Reference::resource("aws_eks_cluster", "main")
+            .attr("certificate_authority")
+            .index(0)
+            .attr("data");
+        assert_eq!(
+            reference.to_string(),
+            "aws_eks_cluster.main.certificate_authority[0].data"
+        );
+    }
+
+    #[test]
+    fn test_reference_renders_as_traversal() {
+        let block = hcl::Block::builder("resource")
+            .add_label("aws_subnet")
+            .add_label("public")
+            .add_attribute(("vpc_id", Reference::resource("aws_vpc", "main").attr("id")))
+            .build();
+
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains("vpc_id = aws_vpc.main.id"));
+    }
+
+    #[test]
+    fn test_hyphenated_labels() {
+        let reference = Reference::resource("aws_vpc", "test-vpc").attr("id");
+        assert_eq!(reference.to_string(), "aws_vpc.test-vpc.id");
+    }
+
+    #[test]
+    fn test_invalid_labels_are_sanitized() {
+        let reference = Reference::resource("aws_s3_bucket", "logs.example.com").attr("arn");
+        assert_eq!(reference.to_string(), "aws_s3_bucket.logs_example_com.arn");
+
+        let reference = Reference::data("aws_s3_bucket", "1-logs").attr("arn");
+        assert_eq!(reference.to_string(), "data.aws_s3_bucket._1-logs.arn");
+    }
+}
@@ -0,0 +1,304 @@
+use crate::error::Error;
+use crate::provider::Provider;
+use crate::terraform::TerraformSettings;
+use hcl::{Block, Body, Expression};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A collection of providers, resources and data sources that renders into a single
+/// Terraform configuration.
+#[derive(Debug, Clone, Default)]
+pub struct Stack {
+    terraform: TerraformSettings,
+    blocks: Vec<Block>,
+}
+
+impl Stack {
+    /// Name of the file written by [`Stack::write`].
+    pub const FILE_NAME: &'static str = "main.tf";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts a provider, resource or data source into a block and adds it to the stack.
+    pub fn add<T>(&mut self, item: T) -> Result<&mut Self, Error>
+    where
+        T: TryInto<Block>,
+        T::Error: Into<Error>,
+    {
+        self.blocks.push(item.try_into().map_err(Into::into)?);
+        Ok(self)
+    }
+
+    /// Adds a provider and records its source and version in the `terraform` settings block.
+    pub fn add_provider<P>(&mut self, provider: P) -> Result<&mut Self, Error>
+    where
+        P: Provider + TryInto<Block>,
+        P::Error: Into<Error>,
+    {
+        self.terraform.require(&provider);
+        self.add(provider)
+    }
+
+    /// Settings rendered into the `terraform` block at the top of the configuration.
+    pub fn terraform(&mut self) -> &mut TerraformSettings {
+        &mut self.terraform
+    }
+
+    /// The blocks added so far, in insertion order.
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// Checks that no two blocks share the same address, and that no local value is
+    /// defined twice across `locals` blocks.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut seen = HashSet::new();
+
+        for address in self.blocks.iter().flat_map(addresses) {
+            if !seen.insert(address.clone()) {
+                return Err(Error::DuplicateBlock { address });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the stack and renders it into a single HCL body.
+    pub fn to_body(&self) -> Result<Body, Error> {
+        Body::try_from(self.clone())
+    }
+
+    /// Validates the stack and renders it as an HCL string.
+    pub fn render(&self) -> Result<String, Error> {
+        Ok(hcl::to_string(&self.to_body()?)?)
+    }
+
+    /// Validates the stack and writes it to `main.tf` in `dir`, creating the directory if
+    /// needed. Returns the path of the written file.
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, Error> {
+        let contents = self.render()?;
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let path = dir.join(Self::FILE_NAME);
+        fs::write(&path, contents)?;
+
+        Ok(path)
+    }
+}
+
+impl TryFrom<Stack> for Body {
+    type Error = Error;
+
+    fn try_from(stack: Stack) -> Result<Self, Self::Error> {
+        stack.validate()?;
+
+        let mut body = Body::builder();
+        if !stack.terraform.is_empty() {
+            body = body.add_block(stack.terraform);
+        }
+
+        Ok(body.add_blocks(stack.blocks).build())
+    }
+}
+
+/// Returns the Terraform addresses a block defines. Most blocks define one, `locals` blocks
+/// define one per value, and blocks that may be repeated define none.
+fn addresses(block: &Block) -> Vec<String> {
+    let labels: Vec<&str> = block.labels().iter().map(|l| l.as_str()).collect();
+
+    let address = match (block.identifier(), labels.as_slice()) {
+        ("resource", [kind, name]) => format!("{}.{}", kind, name),
+        ("data", [kind, name]) => format!("data.{}.{}", kind, name),
+        ("variable", [name]) => format!("var.{}", name),
+        ("output", [name]) => format!("output.{}", name),
+        ("provider", [name]) => match alias(block) {
+            Some(alias) => format!("provider.{}.{}", name, alias),
+            None => format!("provider.{}", name),
+        },
+        ("locals", []) => {
+            return block
+                .body()
+                .attributes()
+                .map(|attr| format!("local.{}", attr.key()))
+                .collect()
+        }
+        _ => return vec![],
+    };
+
+    vec![address]
+}
+
+fn alias(block: &Block) -> Option<&str> {
+    block
+        .body()
+        .attributes()
+        .find(|attr| attr.key() == "alias")
+        .and_then(|attr| match attr.expr() {
+            Expression::String(alias) => Some(alias.as_str()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locals::Locals;
+
+    fn resource(kind: &str, name: &str) -> Block {
+        Block::builder("resource")
+            .add_label(kind)
+            .add_label(name)
+            .build()
+    }
+
+    #[test]
+    fn test_stack_renders_all_blocks() {
+        let mut stack = Stack::new();
+        stack
+            .add(Block::builder("provider").add_label("aws").build())
+            .unwrap()
+            .add(resource("aws_vpc", "main"))
+            .unwrap()
+            .add(resource("aws_subnet", "public"))
+            .unwrap();
+
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.contains(r#"provider "aws" {"#));
+        assert!(hcl.contains(r#"resource "aws_vpc" "main" {"#));
+        assert!(hcl.contains(r#"resource "aws_subnet" "public" {"#));
+    }
+
+    #[test]
+    fn test_stack_rejects_duplicate_resources() {
+        let mut stack = Stack::new();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+
+        match stack.validate() {
+            Err(Error::DuplicateBlock { address }) => assert_eq!(address, "aws_vpc.main"),
+            other => panic!("expected duplicate block error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stack_allows_same_label_across_types() {
+        let mut stack = Stack::new();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+        stack.add(resource("aws_subnet", "main")).unwrap();
+        stack
+            .add(
+                Block::builder("data")
+                    .add_label("aws_vpc")
+                    .add_label("main")
+                    .build(),
+            )
+            .unwrap();
+
+        assert!(stack.validate().is_ok());
+    }
+
+    #[test]
+    fn test_stack_allows_aliased_providers() {
+        let aliased = Block::builder("provider")
+            .add_label("aws")
+            .add_attribute(("alias", "west"))
+            .build();
+
+        let mut stack = Stack::new();
+        stack
+            .add(Block::builder("provider").add_label("aws").build())
+            .unwrap();
+        stack.add(aliased.clone()).unwrap();
+        assert!(stack.validate().is_ok());
+
+        stack.add(aliased).unwrap();
+        assert!(matches!(
+            stack.validate(),
+            Err(Error::DuplicateBlock { address }) if address == "provider.aws.west"
+        ));
+    }
+
+    #[test]
+    fn test_stack_write() {
+        let dir = std::env::temp_dir().join(format!("nexus7-stack-{}", std::process::id()));
+
+        let mut stack = Stack::new();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+
+        let path = stack.write(&dir).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(path, dir.join("main.tf"));
+        assert!(contents.contains(r#"resource "aws_vpc" "main" {"#));
+    }
+
+    #[test]
+    fn test_stack_write_rejects_duplicates() {
+        let dir = std::env::temp_dir().join(format!("nexus7-stack-dup-{}", std::process::id()));
+
+        let mut stack = Stack::new();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+
+        assert!(stack.write(&dir).is_err());
+        assert!(!dir.exists());
+    }
+
+    struct Example;
+
+    impl Provider for Example {
+        const NAME: &'static str = "example";
+        const SOURCE: &'static str = "acme/example";
+        const VERSION: &'static str = "~> 1.2";
+    }
+
+    impl From<Example> for Block {
+        fn from(_: Example) -> Self {
+            Block::builder("provider").add_label("example").build()
+        }
+    }
+
+    #[test]
+    fn test_stack_add_provider_requires_provider() {
+        let mut stack = Stack::new();
+        stack.terraform().required_version = Some(">= 1.5".to_string());
+        stack.add_provider(Example).unwrap();
+
+        let hcl = stack.render().unwrap();
+
+        assert!(hcl.starts_with("terraform {"));
+        assert!(hcl.contains(r#"required_version = ">= 1.5""#));
+        assert!(hcl.contains(r#"source = "acme/example""#));
+        assert!(hcl.contains(r#"provider "example" {"#));
+    }
+
+    #[test]
+    fn test_stack_without_settings_omits_terraform_block() {
+        let mut stack = Stack::new();
+        stack.add(resource("aws_vpc", "main")).unwrap();
+
+        assert!(!stack.render().unwrap().contains("terraform {"));
+    }
+
+    #[test]
+    fn test_stack_rejects_local_defined_in_two_blocks() {
+        let mut first = Locals::new();
+        first.add("name", "a".to_string());
+        let mut second = Locals::new();
+        second.add("name", "b".to_string());
+
+        let mut stack = Stack::new();
+        stack.add(first).unwrap().add(second).unwrap();
+
+        assert!(matches!(
+            stack.render(),
+            Err(Error::DuplicateBlock { address }) if address == "local.name"
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::backend::Backend;
+use crate::provider::{Provider, ProviderRequirement};
+use hcl::{Block, Expression, ObjectKey};
+use std::collections::BTreeMap;
+
+/// Represents the top-level `terraform` settings block.
+#[derive(Debug, Clone, Default)]
+pub struct TerraformSettings {
+    /// Version constraint for Terraform itself, such as `>= 1.5`.
+    pub required_version: Option<String>,
+
+    /// Providers required by the configuration, keyed by local name.
+    pub required_providers: BTreeMap<String, ProviderRequirement>,
+
+    /// Where Terraform stores state. Defaults to the local backend when unset.
+    pub backend: Option<Backend>,
+}
+
+impl TerraformSettings {
+    /// Adds the source and version constraint of `provider` to `required_providers`.
+    /// An existing entry with the same local name is kept.
+    pub fn require<P: Provider>(&mut self, provider: &P) -> &mut Self {
+        self.required_providers
+            .entry(P::NAME.to_string())
+            .or_insert_with(|| provider.requirement());
+        self
+    }
+
+    /// Returns true when rendering would produce an empty `terraform {}` block.
+    pub fn is_empty(&self) -> bool {
+        self.required_version.is_none()
+            && self.required_providers.is_empty()
+            && self.backend.is_none()
+    }
+}
+
+impl From<TerraformSettings> for Block {
+    fn from(settings: TerraformSettings) -> Self {
+        let mut block = Block::builder("terraform");
+
+        if let Some(required_version) = settings.required_version {
+            block = block.add_attribute(("required_version", Expression::String(required_version)));
+        }
+
+        if !settings.required_providers.is_empty() {
+            let mut providers = Block::builder("required_providers");
+
+            for (name, requirement) in settings.required_providers {
+                let mut attrs = vec![(
+                    ObjectKey::from(hcl::Identifier::from("source")),
+                    Expression::String(requirement.source),
+                )];
+
+                if let Some(version) = requirement.version {
+                    attrs.push((
+                        ObjectKey::from(hcl::Identifier::from("version")),
+                        Expression::String(version),
+                    ));
+                }
+
+                providers = providers
+                    .add_attribute((name, Expression::Object(attrs.into_iter().collect())));
+            }
+
+            block = block.add_block(providers.build());
+        }
+
+        if let Some(backend) = settings.backend {
+            block = block.add_block(Block::from(backend));
+        }
+
+        block.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Provider for Example {
+        const NAME: &'static str = "example";
+        const SOURCE: &'static str = "acme/example";
+        const VERSION: &'static str = "~> 1.2";
+    }
+
+    #[test]
+    fn test_terraform_settings_to_hcl() {
+        let mut settings = TerraformSettings {
+            required_version: Some(">= 1.5".to_string()),
+            ..Default::default()
+        };
+        settings.require(&Example);
+
+        let block: Block = settings.into();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"terraform {"#));
+        assert!(hcl.contains(r#"required_version = ">= 1.5""#));
+        assert!(hcl.contains(r#"required_providers {"#));
+        assert!(hcl.contains(r#"example = {"#));
+        assert!(hcl.contains(r#"source = "acme/example""#));
+        assert!(hcl.contains(r#"version = "~> 1.2""#));
+    }
+
+    #[test]
+    fn test_require_keeps_explicit_requirement() {
+        let mut settings = TerraformSettings::default();
+        settings.required_providers.insert(
+            "example".to_string(),
+            ProviderRequirement {
+                source: "acme/example".to_string(),
+                version: Some("= 1.0.0".to_string()),
+            },
+        );
+        settings.require(&Example);
+
+        assert_eq!(
+            settings.required_providers["example"].version.as_deref(),
+            Some("= 1.0.0")
+        );
+    }
+
+    #[test]
+    fn test_empty_settings() {
+        let settings = TerraformSettings::default();
+        assert!(settings.is_empty());
+
+        let block: Block = settings.into();
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(!hcl.contains("required_providers"));
+    }
+
+    #[test]
+    fn test_settings_with_backend() {
+        let settings = TerraformSettings {
+            backend: Some(Backend::Local(crate::backend::Local {
+                path: Some("terraform.tfstate".to_string()),
+                workspace_dir: None,
+            })),
+            ..Default::default()
+        };
+        assert!(!settings.is_empty());
+
+        let block: Block = settings.into();
+        let hcl = hcl::to_string(&block).unwrap();
+        assert!(hcl.contains(r#"backend "local" {"#));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::expression::IntoExpression;
+use crate::reference::Reference;
+use hcl::Expression;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// An attribute value that is either a literal known in Rust or an expression evaluated by
+/// Terraform, such as a variable or local reference.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<T> {
+    Literal(T),
+    Expression(Expression),
+}
+
+impl<T> Value<T> {
+    /// The literal value, or `None` when the value is only known to Terraform.
+    pub fn as_literal(&self) -> Option<&T> {
+        match self {
+            Value::Literal(literal) => Some(literal),
+            Value::Expression(_) => None,
+        }
+    }
+}
+
+impl<T: IntoExpression> IntoExpression for Value<T> {
+    fn into_expression(self) -> Expression {
+        match self {
+            Value::Literal(literal) => literal.into_expression(),
+            Value::Expression(expr) => expr,
+        }
+    }
+}
+
+impl<T: IntoExpression> From<Value<T>> for Expression {
+    fn from(value: Value<T>) -> Self {
+        value.into_expression()
+    }
+}
+
+impl<T> From<Reference> for Value<T> {
+    fn from(reference: Reference) -> Self {
+        Value::Expression(reference.into())
+    }
+}
+
+impl<T> From<Expression> for Value<T> {
+    fn from(expr: Expression) -> Self {
+        Value::Expression(expr)
+    }
+}
+
+impl From<&str> for Value<String> {
+    fn from(literal: &str) -> Self {
+        Value::Literal(literal.to_string())
+    }
+}
+
+/// Implements `From<T> for Value<T>` for literal types. A blanket impl would overlap with
+/// the `Reference` and `Expression` conversions above.
+macro_rules! impl_value_from {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for $crate::value::Value<$ty> {
+                fn from(literal: $ty) -> Self {
+                    $crate::value::Value::Literal(literal)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg_attr(not(feature = "aws"), allow(unused_imports))]
+pub(crate) use impl_value_from;
+
+impl_value_from!(
+    String,
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    i32,
+    i64,
+    f64,
+    Vec<String>,
+    Vec<IpAddr>,
+    HashMap<String, String>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_value() {
+        let value: Value<String> = "10.0.0.0/16".into();
+        assert_eq!(value.as_literal().map(String::as_str), Some("10.0.0.0/16"));
+        assert_eq!(Expression::from(value), Expression::from("10.0.0.0/16"));
+    }
+
+    #[test]
+    fn test_reference_value() {
+        let reference = Reference::resource("aws_vpc", "main").attr("id");
+        let value: Value<String> = reference.clone().into();
+
+        assert!(value.as_literal().is_none());
+        assert_eq!(Expression::from(value), Expression::from(reference));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::error::Error;
+use crate::reference::Reference;
+use hcl::expr::{FuncCall, Variable as Keyword};
+use hcl::{Block, Expression, Identifier, ObjectKey};
+use std::collections::BTreeMap;
+
+/// Names Terraform reserves for its own use in `variable` blocks.
+const RESERVED_NAMES: [&str; 8] = [
+    "source",
+    "version",
+    "providers",
+    "count",
+    "for_each",
+    "lifecycle",
+    "depends_on",
+    "locals",
+];
+
+/// A Terraform type constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    String,
+    Number,
+    Bool,
+    Any,
+    List(Box<Type>),
+    Set(Box<Type>),
+    Map(Box<Type>),
+    Tuple(Vec<Type>),
+    Object(BTreeMap<String, Type>),
+    /// An object attribute that may be omitted, with an optional default.
+    Optional(Box<Type>, Option<Expression>),
+}
+
+impl From<Type> for Expression {
+    fn from(kind: Type) -> Self {
+        let keyword = |name: &str| Expression::Variable(Keyword::unchecked(name));
+        let call = |name: &str, arg: Expression| {
+            Expression::from(FuncCall::builder(name).arg(arg).build())
+        };
+
+        match kind {
+            Type::String => keyword("string"),
+            Type::Number => keyword("number"),
+            Type::Bool => keyword("bool"),
+            Type::Any => keyword("any"),
+            Type::List(inner) => call("list", (*inner).into()),
+            Type::Set(inner) => call("set", (*inner).into()),
+            Type::Map(inner) => call("map", (*inner).into()),
+            Type::Tuple(items) => call(
+                "tuple",
+                Expression::Array(items.into_iter().map(Expression::from).collect()),
+            ),
+            Type::Object(attrs) => call(
+                "object",
+                Expression::Object(
+                    attrs
+                        .into_iter()
+                        .map(|(k, v)| (ObjectKey::from(Identifier::sanitized(k)), v.into()))
+                        .collect(),
+                ),
+            ),
+            Type::Optional(inner, default) => {
+                let mut builder = FuncCall::builder("optional").arg(Expression::from(*inner));
+                if let Some(default) = default {
+                    builder = builder.arg(default);
+                }
+                Expression::from(builder.build())
+            }
+        }
+    }
+}
+
+/// A custom validation rule for an input variable.
+#[derive(Debug, Clone)]
+pub struct Validation {
+    /// Expression that must evaluate to true for the value to be accepted.
+    pub condition: Expression,
+    /// Message shown when `condition` is false.
+    pub error_message: String,
+}
+
+/// Represents a Terraform input variable.
+#[derive(Debug, Clone)]
+pub struct Variable {
+    /// Name of the variable, referenced as `var.<name>`.
+    pub name: String,
+
+    /// Type constraint for accepted values.
+    pub kind: Option<Type>,
+
+    /// Default value, which makes the variable optional.
+    pub default: Option<Expression>,
+
+    /// Documentation for the variable.
+    pub description: Option<String>,
+
+    /// Hides the value in plan and apply output.
+    pub sensitive: Option<bool>,
+
+    /// Whether `null` is accepted as a value. Defaults to `true`.
+    pub nullable: Option<bool>,
+
+    /// Custom validation rules.
+    pub validation: Vec<Validation>,
+}
+
+impl Variable {
+    /// Reference to the variable's value, usable as any attribute value.
+    pub fn reference(&self) -> Reference {
+        Reference::variable(&self.name)
+    }
+
+    /// Checks the variable name and validation rules.
+    pub fn validate(&self) -> Result<(), Error> {
+        if Identifier::new(self.name.as_str()).is_err() {
+            return Err(Error::invalid(
+                "variable",
+                "name",
+                format!("`{}` is not a valid identifier", self.name),
+            ));
+        }
+
+        if RESERVED_NAMES.contains(&self.name.as_str()) {
+            return Err(Error::invalid(
+                "variable",
+                "name",
+                format!("`{}` is reserved by Terraform", self.name),
+            ));
+        }
+
+        if self.validation.iter().any(|v| v.error_message.is_empty()) {
+            return Err(Error::MissingArgument {
+                resource: "variable",
+                argument: "error_message",
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Variable> for Block {
+    type Error = Error;
+
+    fn try_from(variable: Variable) -> Result<Self, Self::Error> {
+        variable.validate()?;
+
+        let mut block = Block::builder("variable").add_label(&variable.name);
+
+        if let Some(kind) = variable.kind {
+            block = block.add_attribute(("type", Expression::from(kind)));
+        }
+
+        if let Some(default) = variable.default {
+            block = block.add_attribute(("default", default));
+        }
+
+        if let Some(description) = variable.description {
+            block = block.add_attribute(("description", Expression::String(description)));
+        }
+
+        if let Some(sensitive) = variable.sensitive {
+            block = block.add_attribute(("sensitive", Expression::Bool(sensitive)));
+        }
+
+        if let Some(nullable) = variable.nullable {
+            block = block.add_attribute(("nullable", Expression::Bool(nullable)));
+        }
+
+        let validation_blocks: Vec<Block> = variable
+            .validation
+            .into_iter()
+            .map(|v| {
+                Block::builder("validation")
+                    .add_attribute(("condition", v.condition))
+                    .add_attribute(("error_message", Expression::String(v.error_message)))
+                    .build()
+            })
+            .collect();
+        block = block.add_blocks(validation_blocks);
+
+        Ok(block.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression;
+
+    fn variable(name: &str) -> Variable {
+        Variable {
+            name: name.to_string(),
+            kind: None,
+            default: None,
+            description: None,
+            sensitive: None,
+            nullable: None,
+            validation: vec![],
+        }
+    }
+
+    #[test]
+    fn test_variable_to_hcl() {
+        let var = Variable {
+            kind: Some(Type::String),
+            default: Some(Expression::from("10.0.0.0/16")),
+            description: Some("CIDR block for the VPC".to_string()),
+            nullable: Some(false),
+            validation: vec![Validation {
+                condition: expression::parse("can(cidrhost(var.cidr_block, 0))").unwrap(),
+                error_message: "Must be a valid IPv4 CIDR block.".to_string(),
+            }],
+            ..variable("cidr_block")
+        };
+
+        assert_eq!(var.reference().to_string(), "var.cidr_block");
+
+        let block = Block::try_from(var).unwrap();
+        let hcl = hcl::to_string(&block).unwrap();
+
+        assert!(hcl.contains(r#"variable "cidr_block" {"#));
+        assert!(hcl.contains("type = string"));
+        assert!(hcl.contains(r#"default = "10.0.0.0/16""#));
+        assert!(hcl.contains(r#"description = "CIDR block for the VPC""#));
+        assert!(hcl.contains("nullable = false"));
+        assert!(hcl.contains("validation {"));
+        assert!(hcl.contains("condition = can(cidrhost(var.cidr_block, 0))"));
+        assert!(hcl.contains(r#"error_message = "Must be a valid IPv4 CIDR block.""#));
+    }
+
+    #[test]
+    fn test_variable_type_constraints() {
+        let cases = vec![
+            (Type::List(Box::new(Type::String)), "list(string)"),
+            (Type::Map(Box::new(Type::Number)), "map(number)"),
+            (
+                Type::Tuple(vec![Type::String, Type::Bool]),
+                "tuple([string, bool])",
+            ),
+            (
+                Type::Object(BTreeMap::from([
+                    ("name".to_string(), Type::String),
+                    (
+                        "size".to_string(),
+                        Type::Optional(Box::new(Type::Number), Some(Expression::from(3))),
+                    ),
+                ])),
+                "object({ name = string, size = optional(number, 3) })",
+            ),
+        ];
+
+        for (kind, expected) in cases {
+            let rendered = hcl::format::to_string(&Expression::from(kind)).unwrap();
+            assert_eq!(
+                rendered.split_whitespace().collect::<String>(),
+                expected.split_whitespace().collect::<String>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sensitive_variable() {
+        let var = Variable {
+            kind: Some(Type::String),
+            sensitive: Some(true),
+            ..variable("db_password")
+        };
+
+        let hcl = hcl::to_string(&Block::try_from(var).unwrap()).unwrap();
+        assert!(hcl.contains("sensitive = true"));
+    }
+
+    #[test]
+    fn test_variable_reserved_name() {
+        assert!(matches!(
+            Block::try_from(variable("count")),
+            Err(Error::InvalidArgument {
+                resource: "variable",
+                argument: "name",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_variable_invalid_name() {
+        assert!(Block::try_from(variable("1st")).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use hcl::structure::BlockBuilder;
+use hcl::{Block, Expression};
+use nexus7::{DataSource, Error, HclResource, MetaArguments, Resource, Value};
+
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "test_network")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+
+    cidr_block: Value<String>,
+
+    description: Option<Value<String>>,
+}
+
+impl Network {
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Timeouts {
+    create: String,
+}
+
+impl From<Timeouts> for Block {
+    fn from(timeouts: Timeouts) -> Self {
+        Block::builder("timeouts")
+            .add_attribute(("create", timeouts.create))
+            .build()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Gateway(String),
+    Instance(String),
+}
+
+impl Target {
+    fn apply(self, block: BlockBuilder) -> BlockBuilder {
+        match self {
+            Target::Gateway(id) => block.add_attribute(("gateway_id", id)),
+            Target::Instance(id) => block.add_attribute(("instance_id", id)),
+        }
+    }
+
+    fn apply_as_list(self, block: BlockBuilder) -> BlockBuilder {
+        let id = match self {
+            Target::Gateway(id) | Target::Instance(id) => id,
+        };
+        block.add_attribute(("target_ids", Expression::Array(vec![id.into()])))
+    }
+}
+
+#[derive(Debug, Clone, HclResource)]
+#[hcl(resource = "test_attachment")]
+struct Attachment<'a> {
+    #[hcl(label, rename = "display_name")]
+    name: String,
+
+    #[hcl(rename = "description")]
+    summary: Option<Value<String>>,
+
+    #[hcl(default = "Value::Literal(\"standard\".to_string())")]
+    tier: Option<Value<String>>,
+
+    #[hcl(reference = "id")]
+    network: &'a Network,
+
+    #[hcl(reference = "cidr_block", rename = "peer_cidr")]
+    peer: Option<&'a Network>,
+
+    #[hcl(reference = "id", rename = "network_ids")]
+    networks: Vec<&'a Network>,
+
+    #[hcl(block)]
+    timeouts: Option<Timeouts>,
+
+    #[hcl(block = "override")]
+    overrides: Vec<Timeouts>,
+
+    #[hcl(flatten)]
+    target: Target,
+
+    #[hcl(flatten = "apply_as_list")]
+    targets: Target,
+
+    #[hcl(skip)]
+    #[allow(dead_code)]
+    notes: String,
+
+    #[hcl(meta)]
+    meta: MetaArguments,
+}
+
+impl Attachment<'_> {
+    fn validate(&self) -> Result<(), Error> {
+        self.meta.validate(Self::TYPE)?;
+        if self.networks.is_empty() {
+            return Err(Error::MissingArgument {
+                resource: Self::TYPE,
+                argument: "network_ids",
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, HclResource)]
+#[hcl(data = "test_network")]
+struct NetworkLookup {
+    #[hcl(label)]
+    name: String,
+
+    tags: Option<Value<String>>,
+}
+
+fn network(name: &str) -> Network {
+    Network {
+        name: name.to_string(),
+        cidr_block: "10.0.0.0/16".to_string().into(),
+        description: None,
+    }
+}
+
+fn attachment<'a>(main: &'a Network, peer: &'a Network) -> Attachment<'a> {
+    Attachment {
+        name: "main".to_string(),
+        summary: None,
+        tier: None,
+        network: main,
+        peer: None,
+        networks: vec![main, peer],
+        timeouts: None,
+        overrides: Vec::new(),
+        target: Target::Gateway("igw-1".to_string()),
+        targets: Target::Instance("i-1".to_string()),
+        notes: "not rendered".to_string(),
+        meta: MetaArguments::default(),
+    }
+}
+
+fn render<T>(value: T) -> String
+where
+    Block: TryFrom<T, Error = Error>,
+{
+    hcl::to_string(&Block::try_from(value).unwrap()).unwrap()
+}
+
+#[test]
+fn test_label_names_the_block_and_implements_resource() {
+    let network = network("main");
+    assert_eq!(<Network as Resource>::TYPE, "test_network");
+    assert_eq!(network.reference().to_string(), "test_network.main");
+
+    let hcl = render(network);
+    assert!(hcl.starts_with(r#"resource "test_network" "main" {"#));
+    assert!(!hcl.contains("name ="));
+    assert!(hcl.contains(r#"cidr_block = "10.0.0.0/16""#));
+    assert!(!hcl.contains("description"));
+}
+
+#[test]
+fn test_label_with_rename_is_also_an_attribute() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(hcl.contains(r#"resource "test_attachment" "main" {"#));
+    assert!(hcl.contains(r#"display_name = "main""#));
+}
+
+#[test]
+fn test_rename_and_default() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(!hcl.contains("description"));
+    assert!(hcl.contains(r#"tier = "standard""#));
+
+    let hcl = render(Attachment {
+        summary: Some("primary".to_string().into()),
+        tier: Some("premium".to_string().into()),
+        ..attachment(&main, &peer)
+    });
+    assert!(hcl.contains(r#"description = "primary""#));
+    assert!(hcl.contains(r#"tier = "premium""#));
+}
+
+#[test]
+fn test_reference() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(hcl.contains("network_id = test_network.main.id"));
+    assert!(!hcl.contains("peer_cidr"));
+    assert!(hcl.contains(
+        r#"network_ids = [
+    test_network.main.id,
+    test_network.peer.id
+  ]"#
+    ));
+
+    let hcl = render(Attachment {
+        peer: Some(&peer),
+        ..attachment(&main, &peer)
+    });
+    assert!(hcl.contains("peer_cidr = test_network.peer.cidr_block"));
+}
+
+#[test]
+fn test_block() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(!hcl.contains("timeouts"));
+    assert!(!hcl.contains("override"));
+
+    let hcl = render(Attachment {
+        timeouts: Some(Timeouts {
+            create: "10m".to_string(),
+        }),
+        overrides: vec![
+            Timeouts {
+                create: "1m".to_string(),
+            },
+            Timeouts {
+                create: "2m".to_string(),
+            },
+        ],
+        ..attachment(&main, &peer)
+    });
+    assert!(hcl.contains("  timeouts {\n    create = \"10m\"\n  }"));
+    assert!(hcl.contains("  override {\n    create = \"1m\"\n  }"));
+    assert!(hcl.contains("  override {\n    create = \"2m\"\n  }"));
+}
+
+#[test]
+fn test_flatten() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(hcl.contains(r#"gateway_id = "igw-1""#));
+    assert!(hcl.contains("target_ids = [\n    \"i-1\"\n  ]"));
+    assert!(!hcl.contains("target ="));
+}
+
+#[test]
+fn test_skip() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(attachment(&main, &peer));
+    assert!(!hcl.contains("notes"));
+    assert!(!hcl.contains("not rendered"));
+}
+
+#[test]
+fn test_meta_is_rendered_last_and_validated() {
+    let (main, peer) = (network("main"), network("peer"));
+    let hcl = render(Attachment {
+        meta: MetaArguments {
+            count: Some(2.into()),
+            depends_on: vec![peer.reference()],
+            ..Default::default()
+        },
+        ..attachment(&main, &peer)
+    });
+    let tier = hcl.find("tier =").unwrap();
+    let count = hcl.find("count = 2").unwrap();
+    let depends_on = hcl
+        .find("depends_on = [\n    test_network.peer\n  ]")
+        .unwrap();
+    assert!(tier < count && count < depends_on);
+
+    let conflicting = Attachment {
+        meta: MetaArguments {
+            count: Some(2.into()),
+            for_each: Some(Expression::Array(Vec::new())),
+            ..Default::default()
+        },
+        ..attachment(&main, &peer)
+    };
+    assert!(matches!(
+        Block::try_from(conflicting),
+        Err(Error::ConflictingArguments {
+            resource: "test_attachment",
+            first: "count",
+            second: "for_each",
+        })
+    ));
+}
+
+#[test]
+fn test_resource_conversion_runs_validate() {
+    let (main, peer) = (network("main"), network("peer"));
+    assert!(matches!(
+        Block::try_from(Attachment {
+            networks: Vec::new(),
+            ..attachment(&main, &peer)
+        }),
+        Err(Error::MissingArgument {
+            argument: "network_ids",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_data_source() {
+    let lookup = NetworkLookup {
+        name: "shared".to_string(),
+        tags: Some("prod".to_string().into()),
+    };
+    assert_eq!(lookup.attr("id").to_string(), "data.test_network.shared.id");
+
+    let hcl = hcl::to_string(&Block::from(lookup)).unwrap();
+    assert!(hcl.starts_with(r#"data "test_network" "shared" {"#));
+    assert!(hcl.contains(r#"tags = "prod""#));
+}
+
+#[test]
+fn test_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,14 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_attachment")]
+struct Attachment {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(block)]
+    #[hcl(flatten)]
+    target: String,
+}
+
+fn main() {}
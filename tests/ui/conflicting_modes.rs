@@ -0,0 +1,13 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_attachment")]
+struct Attachment<'a> {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(reference = "id", skip)]
+    network: &'a Attachment<'a>,
+}
+
+fn main() {}
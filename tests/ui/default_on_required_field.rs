@@ -0,0 +1,13 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_network")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(default = "\"10.0.0.0/16\".to_string()")]
+    cidr_block: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_network")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(rename = "cidr", rename = "cidr_block")]
+    block: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use nexus7::{HclResource, MetaArguments};
+
+#[derive(HclResource)]
+#[hcl(resource = "test_network")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(meta)]
+    meta: MetaArguments,
+
+    #[hcl(meta)]
+    more_meta: MetaArguments,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+struct Network {
+    #[hcl(label)]
+    name: String,
+}
+
+fn main() {}
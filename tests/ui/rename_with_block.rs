@@ -0,0 +1,13 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_attachment")]
+struct Attachment {
+    #[hcl(label)]
+    name: String,
+
+    #[hcl(block, rename = "override")]
+    timeouts: Option<String>,
+}
+
+fn main() {}
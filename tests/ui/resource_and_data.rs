@@ -0,0 +1,10 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_network", data = "test_network")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+}
+
+fn main() {}
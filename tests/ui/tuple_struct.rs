@@ -0,0 +1,7 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(resource = "test_network")]
+struct Network(String);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use nexus7::HclResource;
+
+#[derive(HclResource)]
+#[hcl(provider = "aws")]
+struct Network {
+    #[hcl(label)]
+    name: String,
+}
+
+fn main() {}